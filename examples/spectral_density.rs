@@ -1,7 +1,7 @@
 use rand::prelude::*;
 use rand_distr::StandardNormal;
 use std::time::Instant;
-use welch_sde::{Build, SpectralDensity};
+use welch_sde::{Build, NoiseFloor, SpectralDensity};
 
 fn main() {
     let n = 1e5 as usize;
@@ -25,14 +25,11 @@ fn main() {
         "Spectral density estimated in {}ms",
         now.elapsed().as_millis()
     );
-    let noise_floor = sd.iter().cloned().sum::<f64>() / sd.len() as f64;
-    println!("Noise floor: {:.3}", noise_floor);
+    let (noise_floor, error) = sd.noise_floor(NoiseFloor::Median, Some(1e-2));
+    println!("Noise floor: {:.3}+/-{:.3}", noise_floor, error);
 
     let _: complot::LinLog = (
-        sd.frequency()
-            .into_iter()
-            .zip(&(*sd))
-            .map(|(x, &y)| (x, vec![2. * y])),
+        sd.iter().map(|(x, y)| (x, vec![y])),
         complot::complot!(
             "spectral_density.png",
             xlabel = "Frequency [Hz]",
@@ -24,7 +24,11 @@ fn main() {
         now.elapsed().as_millis()
     );
     {
-        let variance = 2. * ps.iter().sum::<f64>();
+        let variance = ps.iter().map(|(_, p)| p).sum::<f64>();
         println!("Signal variance from power spectrum: {:.3}", variance);
+        println!(
+            "Signal variance from the spectral density: {:.3}",
+            welch.variance()
+        );
     }
 }
@@ -0,0 +1,199 @@
+use crate::{periodogram::one_sided, Build, Builder, One, Periodogram, Signal, Welch};
+use num_complex::Complex;
+use rustfft::FftPlanner;
+use std::fmt::Display;
+
+/// Autoregressive model estimation method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArMethod {
+    /// Burg method (default)
+    #[default]
+    Burg,
+    /// Yule-Walker method
+    YuleWalker,
+}
+
+/// Autoregressive spectral density
+///
+/// Fits an autoregressive model of order `p`, `x[i] + a[1]x[i-1] + ... + a[p]x[i-p] = e[i]`,
+/// to the signal without its mean and computes the spectral density of the model `s^2/(fs |A(f)|^2)`
+/// where `s^2` is the variance of the white noise `e` and `A` is the Fourier transform of `[1,a[1],...,a[p]]`.
+/// The spectral density is evaluated on the same frequency grid than the [Welch] [Periodogram]
+/// with the same [Builder] settings.
+/// The model order is `p=10`, or `n-1` for a signal of `n<=10` samples, and the method is [ArMethod::Burg] unless set with
+/// [ArSpectrum::order] and [ArSpectrum::method].
+/// The mean of the signal is removed before the model is fitted, such as the spectral density
+/// does not include the power of the signal mean at the zero frequency.
+#[derive(Debug, Clone)]
+pub struct ArSpectrum<'a, T: Signal> {
+    welch: Welch<'a, T, One<T>>,
+    order: usize,
+    method: ArMethod,
+}
+impl<'a, T: Signal> ArSpectrum<'a, T> {
+    /// Returns [Welch] [Builder] given the `signal` sampled at `fs`Hz
+    pub fn builder(signal: &[T], fs: T) -> Builder<'_, T, One<T>> {
+        Builder::new(signal).sampling_frequency(fs)
+    }
+    /// Sets the model order `p`
+    ///
+    /// Panics if the order is not less than the number of samples of the signal
+    pub fn order(self, order: usize) -> Self {
+        let n = self.welch.signal.len();
+        assert!(
+            order < n,
+            "the model order ({}) must be less than the number of samples ({})",
+            order,
+            n
+        );
+        Self { order, ..self }
+    }
+    /// Sets the model estimation method
+    pub fn method(self, method: ArMethod) -> Self {
+        Self { method, ..self }
+    }
+    /// Returns the model coefficients `[1,a[1],...,a[p]]` and the white noise variance
+    ///
+    /// The coefficients are fitted to the signal without its mean
+    pub fn coefficients(&self) -> (Vec<T>, T) {
+        let x = self.welch.signal.contiguous();
+        let mean = x.iter().fold(T::zero(), |s, &x| s + x) / T::from_usize(x.len()).unwrap();
+        let x: Vec<T> = x.iter().map(|&x| x - mean).collect();
+        match self.method {
+            ArMethod::Burg => burg(&x, self.order),
+            ArMethod::YuleWalker => yule_walker(&x, self.order),
+        }
+    }
+    /// Returns the autoregressive spectral density periodogram
+    pub fn periodogram(&self) -> Periodogram<T> {
+        let m = self.welch.dft_size;
+        let (a, s2) = self.coefficients();
+        let mut buffer: Vec<Complex<T>> = a
+            .into_iter()
+            .map(|a| Complex::new(a, T::zero()))
+            .chain(std::iter::repeat(Complex::new(T::zero(), T::zero())))
+            .take(m)
+            .collect();
+        FftPlanner::new().plan_fft_forward(m).process(&mut buffer);
+        let fs = self.welch.fs;
+        let mut values: Vec<T> = buffer
+            .into_iter()
+            .take(self.welch.n_bins())
+            .map(|x| s2 / (fs * x.norm_sqr()))
+            .collect();
+        if !self.welch.is_two_sided() {
+            one_sided(&mut values, m);
+        }
+        self.welch.to_periodogram(values)
+    }
+}
+// Yule-Walker estimate of the model coefficients with the Levinson-Durbin recursion
+fn yule_walker<T: Signal>(x: &[T], p: usize) -> (Vec<T>, T) {
+    let n = T::from_usize(x.len()).unwrap();
+    let r: Vec<T> = (0..=p)
+        .map(|k| x.iter().zip(&x[k..]).map(|(&a, &b)| a * b).sum::<T>() / n)
+        .collect();
+    let mut a = vec![T::one()];
+    let mut e = r[0];
+    for k in 1..=p {
+        let acc = (1..k).fold(r[k], |acc, j| acc + a[j] * r[k - j]);
+        let kappa = -acc / e;
+        a.push(T::zero());
+        let b = a.clone();
+        (1..=k).for_each(|j| a[j] += kappa * b[k - j]);
+        e = e * (T::one() - kappa * kappa);
+    }
+    (a, e)
+}
+// Burg estimate of the model coefficients
+fn burg<T: Signal>(x: &[T], p: usize) -> (Vec<T>, T) {
+    let n = x.len();
+    let mut f = x.to_vec();
+    let mut b = x.to_vec();
+    let mut a = vec![T::one()];
+    let mut e = x.iter().map(|&x| x * x).sum::<T>() / T::from_usize(n).unwrap();
+    let two = T::from_f64(2.).unwrap();
+    for k in 1..=p {
+        let (num, den) = (k..n).fold((T::zero(), T::zero()), |(num, den), i| {
+            (
+                num + f[i] * b[i - 1],
+                den + f[i] * f[i] + b[i - 1] * b[i - 1],
+            )
+        });
+        let kappa = -two * num / den;
+        a.push(T::zero());
+        let c = a.clone();
+        (1..=k).for_each(|j| a[j] += kappa * c[k - j]);
+        for i in (k..n).rev() {
+            let fi = f[i];
+            f[i] += kappa * b[i - 1];
+            b[i] = b[i - 1] + kappa * fi;
+        }
+        e = e * (T::one() - kappa * kappa);
+    }
+    (a, e)
+}
+impl<'a, T: Signal> Build<ArSpectrum<'a, T>> for Builder<'a, T, One<T>> {
+    fn build(&self) -> ArSpectrum<'a, T> {
+        let welch: Welch<'a, T, One<T>> = self.build();
+        ArSpectrum {
+            order: 10.min(welch.signal.len() - 1),
+            welch,
+            method: ArMethod::default(),
+        }
+    }
+}
+impl<'a, T: Signal> Display for ArSpectrum<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Autoregressive spectral density estimator:")?;
+        writeln!(f, " - model order      : {:>6}", self.order)?;
+        writeln!(f, " - method           : {:?}", self.method)?;
+        write!(f, " - dft size         : {:>6}", self.welch.dft_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArMethod, ArSpectrum};
+    use crate::Build;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    // AR(1) process `x[i]=0.9x[i-1]+e[i]` with a unit variance white noise `e`, over an offset
+    fn ar1(n: usize) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(31);
+        let mut x = 0.;
+        (0..n)
+            .map(|_| {
+                let e: f64 = StandardNormal.sample(&mut rng);
+                x = 0.9 * x + e;
+                5. + x
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ar1_spectral_density() {
+        let fs = 10.;
+        let signal = ar1(100_000);
+        for method in [ArMethod::Burg, ArMethod::YuleWalker] {
+            let ar: ArSpectrum<f64> = ArSpectrum::builder(&signal, fs).build();
+            let ar = ar.order(1).method(method);
+            let (a, s2) = ar.coefficients();
+            assert!((a[1] + 0.9).abs() < 1e-2, "{method:?}: {a:?}");
+            assert!((s2 - 1.).abs() < 2e-2, "{method:?}: {s2}");
+            // one-sided spectral density 2/(fs |1-0.9exp(-2i pi f/fs)|^2)
+            ar.periodogram()
+                .iter()
+                .filter(|(f, _)| *f > 0. && *f < fs / 2.)
+                .for_each(|(f, x)| {
+                    let w = 2. * std::f64::consts::PI * f / fs;
+                    let expected = 2. / (fs * (1. - 1.8 * w.cos() + 0.81));
+                    assert!(
+                        (x / expected - 1.).abs() < 0.1,
+                        "{method:?} at {f}Hz: {x} vs {expected}"
+                    );
+                });
+        }
+    }
+}
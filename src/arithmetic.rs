@@ -0,0 +1,117 @@
+use crate::{Periodogram, PeriodogramError, Signal};
+use std::ops::{Add, Div, Mul, Sub};
+
+impl<T: Signal, V: Copy> Periodogram<T, V> {
+    // Checks that `self` and `other` have the same frequency bins
+    //
+    // The first frequencies and the frequency resolutions must match to within 1e-6 of the bin width
+    fn check_frequency(&self, other: &Self) -> Result<(), PeriodogramError> {
+        if self.len() != other.len() {
+            return Err(PeriodogramError::Length {
+                left: self.len(),
+                right: other.len(),
+            });
+        }
+        if self.is_two_sided() != other.is_two_sided() {
+            return Err(PeriodogramError::Sides {
+                left_two_sided: self.is_two_sided(),
+                right_two_sided: other.is_two_sided(),
+            });
+        }
+        let tolerance = T::from_f64(1e-6).unwrap() * self.df().abs();
+        let n = T::from_usize(self.len()).unwrap();
+        if (self.f0() - other.f0()).abs() > tolerance
+            || (self.df() - other.df()).abs() * n > tolerance
+        {
+            return Err(PeriodogramError::Frequency {
+                left_f0: self.f0().to_f64().unwrap(),
+                left_df: self.df().to_f64().unwrap(),
+                right_f0: other.f0().to_f64().unwrap(),
+                right_df: other.df().to_f64().unwrap(),
+            });
+        }
+        Ok(())
+    }
+    // Applies `op` to the values of `self` and `other` bin by bin
+    fn zip_with<F: Fn(V, V) -> V>(
+        &self,
+        other: &Self,
+        op: F,
+    ) -> Result<Periodogram<T, V>, PeriodogramError> {
+        self.check_frequency(other)?;
+        Ok(self.with_values(
+            self.iter()
+                .zip(other.iter())
+                .map(|((_, a), (_, b))| op(a, b))
+                .collect(),
+        ))
+    }
+}
+/// Element-wise sum of periodograms with the same frequencies
+///
+/// The degrees of freedom of the result are unknown.
+impl<T: Signal, V: Copy + Add<Output = V>> Add for &Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+/// Element-wise difference of periodograms with the same frequencies, e.g. for background subtraction
+///
+/// The degrees of freedom of the result are unknown.
+impl<T: Signal, V: Copy + Sub<Output = V>> Sub for &Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+/// Element-wise product of periodograms with the same frequencies
+///
+/// The degrees of freedom of the result are unknown.
+impl<T: Signal, V: Copy + Mul<Output = V>> Mul for &Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a * b)
+    }
+}
+/// Element-wise ratio of periodograms with the same frequencies
+///
+/// The degrees of freedom of the result are unknown.
+impl<T: Signal, V: Copy + Div<Output = V>> Div for &Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a / b)
+    }
+}
+impl<T: Signal, V: Copy + Add<Output = V>> Add for Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+impl<T: Signal, V: Copy + Sub<Output = V>> Sub for Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        &self - &rhs
+    }
+}
+impl<T: Signal, V: Copy + Mul<Output = V>> Mul for Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+impl<T: Signal, V: Copy + Div<Output = V>> Div for Periodogram<T, V> {
+    type Output = Result<Periodogram<T, V>, PeriodogramError>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        &self / &rhs
+    }
+}
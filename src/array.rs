@@ -0,0 +1,99 @@
+use crate::{
+    Builder, Coherogram, Periodogram, Periodogram2d, Sample, Signal, Waterfall, Welch, Welch2d,
+    WelchError, Window,
+};
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Builder<'a, T, W, S> {
+    /// Creates a Welch [Builder] from a given signal array view, without copying the signal
+    ///
+    /// Panics if the signal samples are not contiguous in memory, e.g. for a column of an array in row major order,
+    /// in that case the view must be copied into a contiguous array first with `to_owned`
+    pub fn from_array(signal: ArrayView1<'a, S>) -> Self {
+        Self::new(
+            signal
+                .to_slice()
+                .expect("the signal array samples must be contiguous in memory"),
+        )
+    }
+}
+impl<T: Signal, V: Clone> Periodogram<T, V> {
+    /// Returns the periodogram values as an [Array1]
+    pub fn to_array(&self) -> Array1<V> {
+        Array1::from(self.to_vec())
+    }
+    /// Returns the periodogram values as an [Array1], consuming the periodogram
+    pub fn into_array(self) -> Array1<V> {
+        Array1::from(self.into_inner().1)
+    }
+    /// Returns the frequency vector in Hz as an [Array1] (see [Periodogram::frequency])
+    pub fn frequency_array(&self) -> Array1<T> {
+        Array1::from(self.frequency())
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
+    /// Returns the periodogram of each segment as an [Array2] with one row per segment and one column per frequency bin
+    ///
+    /// The rows are the periodograms given by [Welch::segment_periodograms], i.e. a spectrogram of the signal
+    /// with a time step of `l(1-a)/fs`
+    pub fn segment_periodograms_array(&self) -> Array2<T> {
+        let segments = self.segment_periodograms();
+        let n = segments.first().map_or(0, |p| p.len());
+        Array2::from_shape_vec(
+            (segments.len(), n),
+            segments
+                .into_iter()
+                .flat_map(|p| p.into_inner().1)
+                .collect(),
+        )
+        .unwrap()
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Waterfall<'a, T, W, S> {
+    /// Returns the periodogram of each block as an [Array2] with one row per block and one column per frequency bin
+    ///
+    /// The rows are the periodograms given by [Waterfall::periodograms] at the times given by [Waterfall::time]
+    pub fn periodograms_array(&self) -> Result<Array2<T>, WelchError> {
+        let blocks = self.periodograms()?;
+        let n = blocks.first().map_or(0, |p| p.len());
+        Ok(Array2::from_shape_vec(
+            (blocks.len(), n),
+            blocks.into_iter().flat_map(|p| p.into_inner().1).collect(),
+        )
+        .unwrap())
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Coherogram<'a, T, W> {
+    /// Returns the coherence of each block as an [Array2] with one row per block and one column per frequency bin
+    ///
+    /// The rows are the coherences given by [Coherogram::coherence] at the times given by [Coherogram::time]
+    pub fn coherence_array(&self) -> Result<Array2<T>, WelchError> {
+        let blocks = self.coherence()?;
+        let n = blocks.first().map_or(0, |p| p.len());
+        Ok(Array2::from_shape_vec(
+            (blocks.len(), n),
+            blocks.into_iter().flat_map(|p| p.into_inner().1).collect(),
+        )
+        .unwrap())
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Welch2d<'a, T, W> {
+    /// Creates a [Welch2d] estimator from a given 2D array view, without copying the samples
+    ///
+    /// Panics if the array samples are not contiguous in memory and in row major order,
+    /// in that case the view must be copied into a standard layout array first with `as_standard_layout`
+    pub fn from_array(data: ArrayView2<'a, T>) -> Self {
+        let shape = data.dim();
+        Self::new(
+            data.to_slice()
+                .expect("the array samples must be contiguous in memory and in row major order"),
+            shape,
+        )
+    }
+}
+impl<T: Signal> Periodogram2d<T> {
+    /// Returns the periodogram values as an [Array2] with the frequencies along the first axis in rows
+    pub fn to_array(&self) -> Array2<T> {
+        Array2::from_shape_vec(self.shape(), self.to_vec()).unwrap()
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{Builder, Periodogram, Signal, Window};
+use arrow_array::{
+    Array, ArrowNativeTypeOp, ArrowPrimitiveType, Float64Array, PrimitiveArray, RecordBatch,
+};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use std::sync::Arc;
+
+impl<'a, T: Signal + ArrowNativeTypeOp, W: Window<T>> Builder<'a, T, W> {
+    /// Creates a Welch [Builder] from an Arrow array, e.g. a `Float64Array` column, without copying the signal
+    ///
+    /// Panics if the array contains null values
+    pub fn from_arrow<P: ArrowPrimitiveType<Native = T>>(signal: &'a PrimitiveArray<P>) -> Self {
+        assert_eq!(
+            signal.null_count(),
+            0,
+            "the signal array must not contain null values"
+        );
+        Self::new(signal.values())
+    }
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the periodogram as an Arrow record batch with the `frequency` `[Hz]` and `value` columns of type `Float64`
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ArrowError> {
+        let schema = Schema::new(vec![
+            Field::new("frequency", DataType::Float64, false),
+            Field::new("value", DataType::Float64, false),
+        ]);
+        let frequency: Float64Array = self
+            .frequency()
+            .into_iter()
+            .map(|f| f.to_f64().unwrap())
+            .collect::<Vec<f64>>()
+            .into();
+        let value: Float64Array = self
+            .iter()
+            .map(|(_, x)| x.to_f64().unwrap())
+            .collect::<Vec<f64>>()
+            .into();
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(frequency), Arc::new(value)])
+    }
+}
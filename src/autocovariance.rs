@@ -0,0 +1,55 @@
+use crate::{Periodogram, Signal};
+use num_complex::Complex;
+use rustfft::FftPlanner;
+
+impl<T: Signal> Periodogram<T> {
+    /// Returns the autocovariance of the signal from lag `0` to lag `m/2`, the inverse Fourier transform of the spectral density
+    ///
+    /// According to the Wiener-Khinchin theorem, the autocovariance at lag `k` is the sum over the two-sided spectrum
+    /// of `S(f) exp(2i pi f k/fs) df`, `S` being the spectral density and `df=fs/m` the width of the frequency bins.
+    /// The values of a one-sided periodogram, except at the zero and Nyquist frequencies, are halved
+    /// and mirrored to the negative frequencies, and the autocovariance at lag `0` is the signal variance.
+    /// The lag `k` corresponds to the time `k/fs`, and the autocovariance at negative lags is symmetric.
+    /// For the two-sided periodogram of a complex signal, the real part of the autocovariance is returned.
+    ///
+    /// The periodogram is assumed to be a spectral density ([Scaling::Density](crate::Scaling::Density)),
+    /// the autocovariance of a power spectrum being obtained by dividing with [Periodogram::df].
+    ///
+    /// Panics if the periodogram frequencies are not the frequencies `i fs/m` of a discrete Fourier transform,
+    /// like for the periodograms of a frequency band ([Welch::zoom](crate::Welch::zoom)).
+    pub fn autocovariance(&self) -> Vec<T> {
+        let n = self.len();
+        let df = self.df();
+        let m = (self.fs() / df).round().to_usize().unwrap_or_default();
+        let (first, dft_grid) = if self.is_two_sided() {
+            (-T::from_usize(m / 2).unwrap() * df, n == m)
+        } else {
+            (T::zero(), n == m / 2 + 1)
+        };
+        assert!(
+            dft_grid && m > 0 && self.f0() == first,
+            "the periodogram frequencies must be the frequencies of a discrete Fourier transform"
+        );
+        // two-sided spectrum in the order of the discrete Fourier transform
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); m];
+        if self.is_two_sided() {
+            buffer
+                .iter_mut()
+                .zip(&self[..])
+                .for_each(|(b, &x)| b.re = x * df);
+            buffer.rotate_left(m / 2);
+        } else {
+            let half = T::from_f64(0.5).unwrap();
+            self[..].iter().enumerate().for_each(|(i, &x)| {
+                if i == 0 || 2 * i == m {
+                    buffer[i].re = x * df;
+                } else {
+                    buffer[i].re = x * df * half;
+                    buffer[m - i].re = x * df * half;
+                }
+            });
+        }
+        FftPlanner::new().plan_fft_inverse(m).process(&mut buffer);
+        buffer.into_iter().take(m / 2 + 1).map(|x| x.re).collect()
+    }
+}
@@ -0,0 +1,16 @@
+/// Computation backend of the segments discrete Fourier transform and of the periodograms accumulation
+///
+/// The backend is set with [Builder::backend](crate::Builder::backend) and it is used by
+/// [Welch::periodogram](crate::Welch::periodogram) and [Welch::segment_periodograms](crate::Welch::segment_periodograms).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Backend {
+    /// CPU with `rustfft` and `realfft`
+    #[default]
+    Cpu,
+    /// CUDA GPU, given by its device ordinal, with cuFFT (requires the `cuda` feature)
+    ///
+    /// The segments are detrended and windowed on the CPU, transferred to the GPU by batches,
+    /// Fourier transformed in double precision, and their squared magnitude is accumulated on the GPU.
+    #[cfg(feature = "cuda")]
+    Cuda(usize),
+}
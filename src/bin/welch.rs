@@ -0,0 +1,311 @@
+//! Spectral density and power spectrum estimation of a signal read from a file
+//!
+//! The signal is read from a CSV, a raw binary (little-endian `f32` or `f64` samples) or a WAV file,
+//! the estimator is set with the `scipy.signal.welch` parameters (see [Scipy]),
+//! and the periodogram is written as CSV, as JSON or as an ASCII plot.
+//!
+//! ```shell
+//! welch --fs 1000 --nperseg 1024 --window hamming signal.csv
+//! welch --output plot recording.wav
+//! ```
+
+use clap::{Parser, ValueEnum};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+};
+use welch_sde::{
+    Bartlett, Blackman, BlackmanHarris, Detrend, Hamming, Hann, One, Periodogram, Scaling, Scipy,
+    WavSignal, Welch, Window,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// comma-separated values, one sample per line
+    Csv,
+    /// raw little-endian 32 bits floating point samples
+    F32,
+    /// raw little-endian 64 bits floating point samples
+    F64,
+    /// WAV audio file
+    Wav,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum WindowKind {
+    Hann,
+    Hamming,
+    Blackman,
+    BlackmanHarris,
+    Bartlett,
+    Rectangular,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum ScalingKind {
+    Density,
+    Spectrum,
+    RmsAmplitude,
+    PeakAmplitude,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum DetrendKind {
+    None,
+    Constant,
+    Linear,
+}
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Output {
+    /// `frequency,value` lines
+    Csv,
+    /// `{"frequency": [...], "value": [...]}` object
+    Json,
+    /// ASCII plot of the periodogram in dB
+    Plot,
+}
+
+/// Spectral density and power spectrum estimation with Welch method
+#[derive(Debug, Parser)]
+#[command(name = "welch", version)]
+struct Args {
+    /// input file, the standard input if `-`
+    input: PathBuf,
+    /// input format, guessed from the file extension if not given (`csv` by default)
+    #[arg(short = 'f', long)]
+    format: Option<Format>,
+    /// column of the samples in a CSV file, starting from 0
+    #[arg(long, default_value_t = 0)]
+    column: usize,
+    /// channel of a WAV file, starting from 0
+    #[arg(long, default_value_t = 0)]
+    channel: usize,
+    /// sampling frequency in Hz, read from the header of WAV files
+    #[arg(long)]
+    fs: Option<f64>,
+    /// length of each segment
+    #[arg(long)]
+    nperseg: Option<usize>,
+    /// number of samples to overlap between segments
+    #[arg(long)]
+    noverlap: Option<usize>,
+    /// length of the discrete Fourier transform
+    #[arg(long)]
+    nfft: Option<usize>,
+    /// segments window
+    #[arg(short, long, value_enum, default_value_t = WindowKind::Hann)]
+    window: WindowKind,
+    /// periodogram scaling
+    #[arg(short, long, value_enum, default_value_t = ScalingKind::Density)]
+    scaling: ScalingKind,
+    /// segments detrending
+    #[arg(long, value_enum, default_value_t = DetrendKind::Constant)]
+    detrend: DetrendKind,
+    /// returns the two-sided spectrum
+    #[arg(long)]
+    two_sided: bool,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = Output::Csv)]
+    output: Output,
+    /// writes the CSV and JSON values in dB relative to this reference
+    #[arg(long)]
+    db: Option<f64>,
+    /// output file, the standard output if not given
+    #[arg(long)]
+    out: Option<PathBuf>,
+    /// width of the ASCII plot in characters
+    #[arg(long, default_value_t = 72)]
+    width: usize,
+    /// height of the ASCII plot in lines
+    #[arg(long, default_value_t = 20)]
+    height: usize,
+}
+
+fn main() {
+    if let Err(e) = run(Args::parse()) {
+        eprintln!("welch: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let (signal, file_fs) = read_signal(&args)?;
+    let fs = args.fs.or(file_fs).unwrap_or(1.);
+    let periodogram = match args.window {
+        WindowKind::Hann => estimate::<Hann<f64>>(&args, &signal, fs)?,
+        WindowKind::Hamming => estimate::<Hamming<f64>>(&args, &signal, fs)?,
+        WindowKind::Blackman => estimate::<Blackman<f64>>(&args, &signal, fs)?,
+        WindowKind::BlackmanHarris => estimate::<BlackmanHarris<f64>>(&args, &signal, fs)?,
+        WindowKind::Bartlett => estimate::<Bartlett<f64>>(&args, &signal, fs)?,
+        WindowKind::Rectangular => estimate::<One<f64>>(&args, &signal, fs)?,
+    };
+    let mut writer: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+    match args.output {
+        Output::Csv => periodogram.to_csv(&mut writer, args.db)?,
+        Output::Json => {
+            let values = match args.db {
+                Some(reference) => periodogram.to_db(reference).to_vec(),
+                None => periodogram.to_vec(),
+            };
+            let json = serde_json::json!({
+                "fs": fs,
+                "frequency": periodogram.frequency(),
+                "value": values,
+            });
+            serde_json::to_writer(&mut writer, &json)?;
+            writeln!(writer)?;
+        }
+        Output::Plot => plot(&mut writer, &periodogram, args.width, args.height)?,
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+// Estimates the periodogram of the `signal` with the window `W`
+fn estimate<W: Window<f64>>(
+    args: &Args,
+    signal: &[f64],
+    fs: f64,
+) -> Result<Periodogram<f64>, Box<dyn Error>> {
+    let scipy = Scipy {
+        nperseg: args.nperseg,
+        noverlap: args.noverlap,
+        nfft: args.nfft,
+        detrend: Some(match args.detrend {
+            DetrendKind::None => Detrend::None,
+            DetrendKind::Constant => Detrend::Constant,
+            DetrendKind::Linear => Detrend::Linear,
+        }),
+        return_onesided: Some(!args.two_sided),
+        scaling: Some(match args.scaling {
+            ScalingKind::Density => Scaling::Density,
+            ScalingKind::Spectrum => Scaling::Spectrum,
+            ScalingKind::RmsAmplitude => Scaling::RmsAmplitude,
+            ScalingKind::PeakAmplitude => Scaling::PeakAmplitude,
+        }),
+    };
+    let welch: Welch<f64, W> = scipy.builder(signal, fs).try_build()?;
+    Ok(welch.periodogram())
+}
+
+// Reads the signal and, for WAV files, its sampling frequency
+fn read_signal(args: &Args) -> Result<(Vec<f64>, Option<f64>), Box<dyn Error>> {
+    let format = args.format.unwrap_or_else(|| {
+        match args
+            .input
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .as_deref()
+        {
+            Some("wav") => Format::Wav,
+            Some("f32") => Format::F32,
+            Some("f64" | "bin" | "raw") => Format::F64,
+            _ => Format::Csv,
+        }
+    });
+    let mut reader: Box<dyn Read> = if args.input.as_os_str() == "-" {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(&args.input)?)
+    };
+    match format {
+        Format::Wav => {
+            let wav: WavSignal<f64> = WavSignal::from_reader(BufReader::new(reader))?;
+            if args.channel >= wav.n_channel() {
+                return Err(format!(
+                    "the channel {} is not within the {} channels of the file",
+                    args.channel,
+                    wav.n_channel()
+                )
+                .into());
+            }
+            Ok((wav.channel(args.channel).to_vec(), Some(wav.fs)))
+        }
+        Format::F32 | Format::F64 => {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes)?;
+            let signal = if format == Format::F32 {
+                bytes
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes(b.try_into().unwrap()) as f64)
+                    .collect()
+            } else {
+                bytes
+                    .chunks_exact(8)
+                    .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+                    .collect()
+            };
+            Ok((signal, None))
+        }
+        Format::Csv => {
+            // lines without a number in the column, like headers or comments, are skipped
+            let mut signal = Vec::new();
+            for line in BufReader::new(reader).lines() {
+                let line = line?;
+                if let Some(x) = line
+                    .split([',', ';', '\t'])
+                    .nth(args.column)
+                    .and_then(|x| x.trim().parse::<f64>().ok())
+                {
+                    signal.push(x);
+                }
+            }
+            Ok((signal, None))
+        }
+    }
+}
+
+// Plots the periodogram in dB with ASCII characters
+//
+// Each column of the plot is the maximum of the periodogram bins within the column frequency range
+fn plot<W: Write>(
+    writer: &mut W,
+    periodogram: &Periodogram<f64>,
+    width: usize,
+    height: usize,
+) -> io::Result<()> {
+    let db = periodogram.to_db(1.);
+    let n = db.len();
+    let width = width.clamp(1, n);
+    let height = height.max(2);
+    let columns: Vec<f64> = (0..width)
+        .map(|j| {
+            db[j * n / width..((j + 1) * n / width).max(j * n / width + 1)]
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, f64::max)
+        })
+        .collect();
+    let max = columns.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let min = columns.iter().cloned().fold(f64::INFINITY, f64::min);
+    let range = (max - min).max(f64::EPSILON);
+    let rows: Vec<usize> = columns
+        .iter()
+        .map(|&x| ((x - min) / range * (height - 1) as f64).round() as usize)
+        .collect();
+    for i in (0..height).rev() {
+        let label = min + range * i as f64 / (height - 1) as f64;
+        let line: String = rows
+            .iter()
+            .map(|&r| match r {
+                r if r == i => '*',
+                r if r > i => '|',
+                _ => ' ',
+            })
+            .collect();
+        writeln!(writer, "{:>9.1} dB |{}", label, line)?;
+    }
+    let frequency = periodogram.frequency();
+    writeln!(writer, "{:>13}+{}", "", "-".repeat(width))?;
+    writeln!(
+        writer,
+        "{:>14}{:<w$}{:>9.3e} Hz",
+        "",
+        format!("{:.3e}", frequency[0]),
+        frequency[n - 1],
+        w = width.saturating_sub(9)
+    )
+}
@@ -0,0 +1,122 @@
+use crate::{
+    autocorrelation, periodogram::one_sided, Bartlett, Build, Builder, CorrelationBias,
+    Periodogram, Signal, Symmetry, Welch, Window,
+};
+use num_complex::Complex;
+use rustfft::FftPlanner;
+use std::{fmt::Display, ops::Deref};
+
+/// Blackman-Tukey spectral density
+///
+/// Computes a `signal` spectral density with the Blackman-Tukey correlogram method:
+/// the biased autocorrelation of the signal is computed up to the lag `L`, multiplied
+/// by a symmetric lag window of size `2L+1` and Fourier transformed.
+/// The size of the lag window is the segment size `l` of the [Builder] (`L=(l-1)/2`), and the lag window `W`
+/// is a [Bartlett] window by default.
+/// The detrending, the [NanPolicy](crate::NanPolicy) and the validity mask of the [Builder] apply to the whole signal
+/// (see [BlackmanTukey::autocorrelation]).
+#[derive(Debug, Clone)]
+pub struct BlackmanTukey<'a, T: Signal, W: Window<T> = Bartlett<T>>(Welch<'a, T, W>);
+impl<'a, T: Signal, W: Window<T>> BlackmanTukey<'a, T, W> {
+    /// Returns [Welch] [Builder] given the `signal` sampled at `fs`Hz
+    pub fn builder(signal: &[T], fs: T) -> Builder<'_, T, W> {
+        Builder::new(signal).sampling_frequency(fs)
+    }
+    /// Returns the maximum lag `L`
+    pub fn max_lag(&self) -> usize {
+        (self.0.segment_size - 1) / 2
+    }
+    /// Returns the biased autocorrelation of the signal from lag `0` to lag `L`
+    ///
+    /// The signal is detrended according to [Welch::detrend](Welch#structfield.detrend).
+    /// The missing samples, within the gaps of the validity mask ([Builder::mask]) or NaN with [NanPolicy::Skip](crate::NanPolicy::Skip),
+    /// are excluded: the trend is fitted to the valid samples and the autocorrelation at the lag `k`
+    /// is averaged over the pairs of valid samples `k` samples apart, and tapered by `(n-k)/n` as the biased autocorrelation.
+    pub fn autocorrelation(&self) -> Vec<T> {
+        let welch = &self.0;
+        let l = self.max_lag();
+        let mut x = welch.signal.contiguous().into_owned();
+        let Some(missing) = welch.missing_samples() else {
+            welch.detrend.detrend(&mut x);
+            return autocorrelation(&x, l, CorrelationBias::Biased);
+        };
+        let mut valid: Vec<T> = x
+            .iter()
+            .zip(&missing)
+            .filter_map(|(&x, &m)| (!m).then_some(x))
+            .collect();
+        welch.detrend.detrend(&mut valid);
+        let mut valid = valid.into_iter();
+        let (x, v): (Vec<T>, Vec<T>) = missing
+            .iter()
+            .map(|&m| {
+                if m {
+                    (T::zero(), T::zero())
+                } else {
+                    (valid.next().unwrap(), T::one())
+                }
+            })
+            .unzip();
+        let n = T::from_usize(x.len()).unwrap();
+        autocorrelation(&x, l, CorrelationBias::Biased)
+            .into_iter()
+            .zip(autocorrelation(&v, l, CorrelationBias::Biased))
+            .enumerate()
+            .map(|(k, (rx, rv))| {
+                if rv > T::zero() {
+                    rx / rv * (n - T::from_usize(k).unwrap()) / n
+                } else {
+                    T::zero()
+                }
+            })
+            .collect()
+    }
+    /// Returns the spectral density periodogram
+    pub fn periodogram(&self) -> Periodogram<T> {
+        let welch = &self.0;
+        let l = self.max_lag();
+        let m = welch.dft_size;
+        let lag_window = welch.window.resize(2 * l + 1, Symmetry::Symmetric);
+        let w = &lag_window.weights()[l..];
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); m];
+        self.autocorrelation()
+            .into_iter()
+            .zip(w)
+            .enumerate()
+            .for_each(|(k, (r, &w))| {
+                buffer[k].re = r * w;
+                if k > 0 {
+                    buffer[m - k].re = r * w;
+                }
+            });
+        FftPlanner::new().plan_fft_forward(m).process(&mut buffer);
+        let mut values: Vec<T> = buffer
+            .into_iter()
+            .take(welch.n_bins())
+            .map(|x| x.re / welch.fs)
+            .collect();
+        if !welch.is_two_sided() {
+            one_sided(&mut values, m);
+        }
+        welch.to_periodogram(values)
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Build<BlackmanTukey<'a, T, W>> for Builder<'a, T, W> {
+    fn build(&self) -> BlackmanTukey<'a, T, W> {
+        BlackmanTukey(self.build())
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Deref for BlackmanTukey<'a, T, W> {
+    type Target = Welch<'a, T, W>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Display for BlackmanTukey<'a, T, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Blackman-Tukey spectral density estimator:")?;
+        writeln!(f, " - maximum lag      : {:>6}", self.max_lag())?;
+        write!(f, " - dft size         : {:>6}", self.0.dft_size)
+    }
+}
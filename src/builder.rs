@@ -1,34 +1,121 @@
-use crate::Signal;
+use crate::{
+    chunks::Samples, Averaging, Backend, Build, Calibration, Detrend, NanPolicy, Sample, Scaling,
+    Signal, Symmetry, WelchError, Window, WindowCorrection,
+};
+use std::{borrow::Cow, ops::Range};
 
 /// Generic builder
+///
+/// The window `W` applied to the signal segments is either created with [Window::new] or
+/// given with [Builder::window], and resized to the segment size with [Window::resize]
+/// according to the window [Symmetry] (periodic by default).
+/// The signal samples are of type `S`, either real (`S=T`) or complex ([Sample]).
+///
+/// The signal is given as a slice, or as a slice of chunks with [Builder::from_chunks],
+/// since the estimators built from the [Builder] go through the segments each time a periodogram is computed.
+/// A signal given by an iterator is not collected: the [Builder] is created from the signal length with [Builder::from_len]
+/// and the samples are streamed through a [WelchAccumulator](crate::WelchAccumulator) with [Builder::accumulate],
+/// that buffers a single segment at a time.
 #[derive(Debug, Clone)]
-pub struct Builder<'a, T: Signal> {
+pub struct Builder<'a, T: Signal, W: Window<T>, S: Sample<T> = T> {
     /// number of segments (`k`)
     pub(crate) n_segment: usize,
     /// size of each segment (`l`)
     pub(crate) segment_size: usize,
+    /// `true` if the segment size is set with [Builder::segment_size] and `k` is derived from it
+    pub(crate) fixed_segment_size: bool,
     /// segment overlapping fraction (`0<a<1`)
     pub(crate) overlap: f64,
-    /// maximum size of the discrete Fourier transform (`p`)
-    pub(crate) dft_max_size: usize,
-    /// the signal to estimate the spectral density for
-    pub(crate) signal: &'a [T],
+    /// segment overlapping number of samples, derived from the overlapping fraction if `None`
+    pub(crate) overlap_size: Option<usize>,
+    /// maximum size of the discrete Fourier transform (`p`), unbounded if `None`
+    pub(crate) dft_max_size: Option<usize>,
+    /// zero-pads the segments to the next power of 2
+    pub(crate) power_of_two_dft: bool,
+    /// size of the discrete Fourier transform (`m`), derived from the segment size if `None`
+    pub(crate) dft_size: Option<usize>,
+    /// the signal to estimate the spectral density for, either contiguous or split into chunks
+    pub(crate) signal: Samples<'a, S>,
+    /// the second signal for cross spectral estimates
+    pub(crate) cross_signal: Option<&'a [S]>,
     /// the signal sampling frequency `[Hz]`
     pub(crate) fs: Option<T>,
+    /// the segments windowing function
+    pub(crate) window: Option<W>,
+    /// the window symmetry
+    pub(crate) symmetry: Symmetry,
+    /// the segments periodogram averaging method
+    pub(crate) averaging: Averaging,
+    /// the periodogram scaling
+    pub(crate) scaling: Scaling,
+    /// the periodogram window correction
+    pub(crate) correction: WindowCorrection,
+    /// the segments detrending
+    pub(crate) detrend: Detrend<T>,
+    /// returns the two-sided spectrum of real signals
+    pub(crate) two_sided: bool,
+    /// the signal decimation factor
+    pub(crate) decimation: usize,
+    /// the computation backend
+    pub(crate) backend: Backend,
+    /// the handling of the NaN samples
+    pub(crate) nan_policy: NanPolicy,
+    /// the validity of each sample of the signal, `false` within the gaps
+    pub(crate) mask: Option<Cow<'a, [bool]>>,
+    /// the signal calibration
+    pub(crate) calibration: Option<Calibration<T>>,
 }
-impl<'a, T: Signal> Builder<'a, T> {
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Builder<'a, T, W, S> {
     /// Creates a Welch [Builder] from a given signal with `k=4` and `a=0.5`
-    pub fn new(signal: &'a [T]) -> Self {
+    pub fn new(signal: &'a [S]) -> Self {
+        Self::from_samples(signal.into())
+    }
+    /// Creates a Welch [Builder] from a signal split into `chunks` with `k=4` and `a=0.5`
+    ///
+    /// The chunks, like the packets of an acquisition or the 2 halves of a ring buffer, are the consecutive
+    /// parts of the signal.
+    /// The segments span the chunks boundaries, only the segments across a boundary being copied,
+    /// whereas the chunks are concatenated if the signal is [decimated](Builder::decimation).
+    pub fn from_chunks(chunks: &'a [&'a [S]]) -> Self {
+        Self::from_samples(Samples::chunked(chunks))
+    }
+    /// Creates a Welch [Builder] for a signal of `len` samples given by an iterator, with `k=4` and `a=0.5`
+    ///
+    /// The [Builder] holds the signal length but no samples, the segmentation being derived from the length.
+    /// The samples are pushed into the estimator returned by [Builder::accumulate],
+    /// the [Welch](crate::Welch) estimator requiring the signal samples.
+    pub fn from_len(len: usize) -> Self {
+        Self::from_samples(Samples::Length(len))
+    }
+    // Creates a Welch [Builder] from the signal samples with `k=4` and `a=0.5`
+    pub(crate) fn from_samples(signal: Samples<'a, S>) -> Self {
         let k: usize = 4;
         let a: f64 = 0.5;
-        let l = (signal.len() as f64 / (k as f64 * (1. - a) + a)).trunc() as usize;
+        let l = segment_size(signal.len(), k, a);
         Self {
             n_segment: k,
             segment_size: l,
+            fixed_segment_size: false,
             overlap: a,
-            dft_max_size: 4096,
+            overlap_size: None,
+            dft_max_size: Some(4096),
+            power_of_two_dft: true,
+            dft_size: None,
             signal,
+            cross_signal: None,
             fs: None,
+            window: None,
+            symmetry: Symmetry::default(),
+            averaging: Averaging::default(),
+            scaling: Scaling::default(),
+            correction: WindowCorrection::default(),
+            detrend: Detrend::default(),
+            two_sided: false,
+            decimation: 1,
+            backend: Backend::default(),
+            nan_policy: NanPolicy::default(),
+            mask: None,
+            calibration: None,
         }
     }
     /// Sets the signal sampling frequency
@@ -38,33 +125,370 @@ impl<'a, T: Signal> Builder<'a, T> {
             ..self
         }
     }
+    /// Sets the second signal for cross spectral estimates
+    ///
+    /// The signal must have the same length and sampling frequency than the first signal
+    pub fn cross_signal(self, signal: &'a [S]) -> Self {
+        Self {
+            cross_signal: Some(signal),
+            ..self
+        }
+    }
     /// Sets the segment overlapping fraction (`0<a<1`)
+    ///
+    /// The segment size is derived from the number of segments, unless it is set with [Builder::segment_size]
+    /// and then the number of segments is derived from the segment size
     pub fn overlap(self, overlap: f64) -> Self {
-        let k = self.n_segment;
-        let a = overlap;
-        let l = (self.signal.len() as f64 / (k as f64 * (1. - a) + a)).trunc() as usize;
         Self {
-            segment_size: l,
-            overlap: a,
+            overlap,
+            overlap_size: None,
+            ..self
+        }
+        .segmentation()
+    }
+    /// Sets the segment overlap as a number of samples
+    ///
+    /// The overlap is kept to exactly `overlap_size` samples whatever the segment size,
+    /// which must be greater than `overlap_size`
+    pub fn overlap_size(self, overlap_size: usize) -> Self {
+        Self {
+            overlap_size: Some(overlap_size),
             ..self
         }
+        .segmentation()
     }
     /// Sets the number of segments (`k`)
+    ///
+    /// The segment size is derived from the number of segments
     pub fn n_segment(self, n_segment: usize) -> Self {
-        let k = n_segment;
-        let a = self.overlap;
-        let l = (self.signal.len() as f64 / (k as f64 * (1. - a) + a)).trunc() as usize;
         Self {
-            n_segment: k,
-            segment_size: l,
+            n_segment,
+            fixed_segment_size: false,
+            ..self
+        }
+        .segmentation()
+    }
+    /// Sets the size of the segments (`l`)
+    ///
+    /// The number of segments is derived from the segment size with `k=trunc((n-la)/(l(1-a)))`.
+    /// The maximum size of the discrete Fourier transform does not apply to a segment size set with this method.
+    pub fn segment_size(self, segment_size: usize) -> Self {
+        Self {
+            segment_size,
+            fixed_segment_size: true,
+            ..self
+        }
+        .segmentation()
+    }
+    // Derives either the segment size or the number of segments from the other one and from the overlap
+    fn segmentation(self) -> Self {
+        let n = self.signal_len();
+        match (self.fixed_segment_size, self.overlap_size) {
+            (true, Some(v)) => {
+                let l = self.segment_size;
+                let v = v.min(l.saturating_sub(1));
+                Self {
+                    n_segment: n.saturating_sub(v) / (l - v).max(1),
+                    overlap: v as f64 / l.max(1) as f64,
+                    ..self
+                }
+            }
+            (true, None) => Self {
+                n_segment: n_segment(n, self.segment_size, self.overlap),
+                ..self
+            },
+            (false, Some(v)) => {
+                let k = self.n_segment;
+                let l = (n + k.saturating_sub(1) * v) / k.max(1);
+                Self {
+                    segment_size: l,
+                    overlap: v as f64 / l.max(1) as f64,
+                    ..self
+                }
+            }
+            (false, None) => Self {
+                segment_size: segment_size(n, self.n_segment, self.overlap),
+                ..self
+            },
+        }
+    }
+    // Returns the number of segments, the segment size and the size of the discrete Fourier transform
+    pub(crate) fn dimensions(&self) -> (usize, usize, usize) {
+        let mut k = self.n_segment;
+        let mut l = self.segment_size;
+        let mut m = if self.power_of_two_dft {
+            l.next_power_of_two()
+        } else {
+            l
+        };
+        if let Some(dft_max_size) = self
+            .dft_max_size
+            .filter(|&max| m > max && !self.fixed_segment_size)
+        {
+            l = dft_max_size;
+            k = n_segment(self.signal_len(), l, self.overlap);
+            m = l;
+        }
+        (k, l, self.dft_size.unwrap_or(m))
+    }
+    // Returns the number of samples between the starting points of 2 consecutive segments of size `l`
+    pub(crate) fn overlap_idx(&self, l: usize) -> usize {
+        l - self.overlap_size.map_or_else(
+            || (l as f64 * self.overlap).round() as usize,
+            |v| v.min(l.saturating_sub(1)),
+        )
+    }
+    // Length of the signal after decimation
+    pub(crate) fn signal_len(&self) -> usize {
+        self.signal.len().div_ceil(self.decimation.max(1))
+    }
+    /// Checks the builder settings
+    pub fn validate(&self) -> Result<(), WelchError> {
+        if self.signal.is_empty() {
+            return Err(WelchError::EmptySignal);
+        }
+        if let Some(y) = self.cross_signal.filter(|y| y.len() != self.signal.len()) {
+            return Err(WelchError::CrossSignalLength {
+                signal_len: self.signal.len(),
+                cross_signal_len: y.len(),
+            });
+        }
+        if self.cross_signal.is_some() {
+            if self.averaging != Averaging::Mean {
+                return Err(WelchError::UnsupportedCrossSetting("averaging method"));
+            }
+            if self.scaling.is_amplitude() {
+                return Err(WelchError::UnsupportedCrossSetting("amplitude scaling"));
+            }
+        }
+        if self.decimation == 0 {
+            return Err(WelchError::InvalidDecimation);
+        }
+        let n = self.signal_len();
+        if let Some(fs) = self.fs.filter(|&fs| !(fs > T::zero() && fs.is_finite())) {
+            return Err(WelchError::InvalidSamplingFrequency(fs.to_f64().unwrap()));
+        }
+        if !(0f64..1.).contains(&self.overlap) {
+            return Err(WelchError::InvalidOverlap(self.overlap));
+        }
+        if let Averaging::TrimmedMean(x) | Averaging::WinsorizedMean(x) = self.averaging {
+            if !(0f64..0.5).contains(&x) {
+                return Err(WelchError::InvalidAveraging(x));
+            }
+        }
+        let (k, l, m) = self.dimensions();
+        if l == 0 || l > n {
+            return Err(WelchError::InvalidSegmentSize {
+                segment_size: l,
+                signal_len: n,
+            });
+        }
+        match self.overlap_size {
+            Some(v) if v >= l => {
+                return Err(WelchError::InvalidOverlapSize {
+                    overlap_size: v,
+                    segment_size: l,
+                })
+            }
+            None if self.overlap_idx(l) == 0 => {
+                return Err(WelchError::InvalidOverlap(self.overlap))
+            }
+            _ => (),
+        }
+        if k == 0 {
+            return Err(WelchError::NoSegment);
+        }
+        if m < l {
+            return Err(WelchError::InvalidDftSize {
+                dft_size: m,
+                segment_size: l,
+            });
+        }
+        if let Some(window) = self
+            .window
+            .as_ref()
+            .filter(|w| w.fixed_size() && w.weights().len() != l)
+        {
+            return Err(WelchError::WindowSize {
+                window_size: window.weights().len(),
+                segment_size: l,
+            });
+        }
+        if let Some(window) = self.window.as_ref().filter(|w| l < w.min_size()) {
+            return Err(WelchError::WindowMinSize {
+                min_size: window.min_size(),
+                segment_size: l,
+            });
+        }
+        if let Some(mask) = self
+            .mask
+            .as_deref()
+            .filter(|m| m.len() != self.signal.len())
+        {
+            return Err(WelchError::MaskLength {
+                mask_len: mask.len(),
+                signal_len: self.signal.len(),
+            });
+        }
+        let segments = (l, self.overlap_idx(l), self.decimation);
+        self.nan_policy
+            .check(&self.signal, self.mask.as_deref(), segments)?;
+        if let Some(y) = self.cross_signal {
+            self.nan_policy
+                .check_cross(&self.signal, &y.into(), self.mask.as_deref(), segments)?;
+        }
+        match self.backend {
+            Backend::Cpu => (),
+            #[cfg(feature = "cuda")]
+            Backend::Cuda(ordinal) => crate::cuda::CudaPlan::check_device(ordinal)?,
+        }
+        Ok(())
+    }
+    /// Returns the estimator `E` initialized according to the [Builder] settings or an error if the settings are invalid
+    ///
+    /// [Build::build] panics with the same errors
+    pub fn try_build<E>(&self) -> Result<E, WelchError>
+    where
+        Self: Build<E>,
+    {
+        self.validate().map(|_| self.build())
+    }
+    /// Sets the segments windowing function
+    ///
+    /// The window is resized to the segment size when the estimator is built
+    pub fn window(self, window: W) -> Self {
+        Self {
+            window: Some(window),
+            ..self
+        }
+    }
+    /// Sets the window [Symmetry]
+    pub fn window_symmetry(self, symmetry: Symmetry) -> Self {
+        Self { symmetry, ..self }
+    }
+    /// Sets the segments [Detrend] method
+    pub fn detrend(self, detrend: Detrend<T>) -> Self {
+        Self { detrend, ..self }
+    }
+    /// Sets the segments periodogram [Averaging] method
+    pub fn averaging(self, averaging: Averaging) -> Self {
+        Self { averaging, ..self }
+    }
+    /// Sets the periodogram [Scaling] ([Scaling::Density] by default)
+    pub fn scaling(self, scaling: Scaling) -> Self {
+        Self { scaling, ..self }
+    }
+    /// Sets the periodogram [WindowCorrection] ([WindowCorrection::Auto] by default)
+    pub fn window_correction(self, correction: WindowCorrection) -> Self {
+        Self { correction, ..self }
+    }
+    /// Decimates the signal by `factor` before the segmentation (`1` by default)
+    ///
+    /// The signal is low-pass filtered with a Hamming windowed sinc FIR filter of `20 factor+1` taps with the cutoff frequency
+    /// at the decimated Nyquist frequency, and one sample every `factor` samples is kept.
+    /// The sampling frequency of the estimator is divided by `factor` and the segmentation
+    /// ([Builder::segment_size], [Builder::overlap_size], ...) applies to the decimated signal.
+    pub fn decimation(self, factor: usize) -> Self {
+        Self {
+            decimation: factor,
+            ..self
+        }
+        .segmentation()
+    }
+    /// Sets the handling of the NaN samples of the signal ([NanPolicy::Propagate] by default)
+    pub fn nan_policy(self, nan_policy: NanPolicy) -> Self {
+        Self { nan_policy, ..self }
+    }
+    /// Sets the [Calibration] of the signal
+    ///
+    /// The periodograms of [Welch](crate::Welch) are multiplied by the power gain of the calibration `|H(f)|²`
+    /// at the frequency of each bin, such as the periodograms are given in the calibrated unit
+    pub fn calibration(self, calibration: Calibration<T>) -> Self {
+        Self {
+            calibration: Some(calibration),
+            ..self
+        }
+    }
+    /// Sets the validity `mask` of the signal samples, `false` for the samples within the gaps of the signal
+    ///
+    /// The segments with invalid samples, including the samples within the span of the [decimation](Builder::decimation) filter,
+    /// are skipped and the periodogram is averaged over the remaining segments.
+    /// The mask must have the same length than the signal.
+    pub fn mask(self, mask: &'a [bool]) -> Self {
+        Self {
+            mask: Some(Cow::Borrowed(mask)),
+            ..self
+        }
+    }
+    /// Sets the gaps of the signal as intervals of sample indices, see [Builder::mask]
+    ///
+    /// The gaps are added to the invalid samples of the mask, if it is already set
+    pub fn gaps(self, gaps: &[Range<usize>]) -> Self {
+        let mut mask = self
+            .mask
+            .clone()
+            .map_or_else(|| vec![true; self.signal.len()], |m| m.into_owned());
+        let n = mask.len();
+        gaps.iter()
+            .for_each(|g| mask[g.start.min(n)..g.end.min(n)].fill(false));
+        Self {
+            mask: Some(Cow::Owned(mask)),
+            ..self
+        }
+    }
+    /// Sets the computation [Backend] of the [Welch](crate::Welch) periodograms ([Backend::Cpu] by default)
+    pub fn backend(self, backend: Backend) -> Self {
+        Self { backend, ..self }
+    }
+    /// Returns the two-sided spectrum of real signals (`false` by default)
+    ///
+    /// The spectrum spans the frequencies from `-fs/2` to `fs/2` and the positive frequencies are not doubled.
+    /// The spectrum of complex signals is always two-sided.
+    pub fn two_sided(self, two_sided: bool) -> Self {
+        Self { two_sided, ..self }
+    }
+    /// Zero-pads the segments to the next power of 2 (`true` by default)
+    ///
+    /// If `false`, the size of the discrete Fourier transform is the segment size
+    pub fn power_of_two_dft(self, power_of_two_dft: bool) -> Self {
+        Self {
+            power_of_two_dft,
+            ..self
+        }
+    }
+    /// Sets the size of the discrete Fourier transform (`m`)
+    ///
+    /// The segments are zero-padded to `m` samples, `m` must be greater or equal to the segment size.
+    /// A size larger than the segment size interpolates the spectrum on a finer frequency grid.
+    pub fn dft_size(self, dft_size: usize) -> Self {
+        Self {
+            dft_size: Some(dft_size),
+            ..self
+        }
+    }
+    /// Removes the upper limit on the size of the discrete Fourier transform
+    ///
+    /// The segment size and the number of segments are then only set by the signal length,
+    /// the overlap and [Builder::n_segment]
+    pub fn unbounded_dft_size(self) -> Self {
+        Self {
+            dft_max_size: None,
             ..self
         }
     }
     /// Sets the log2 of the maximum size of the discrete Fourier transform (`p`)
     pub fn dft_log2_max_size(self, dft_log2_max_size: usize) -> Self {
         Self {
-            dft_max_size: 2 << (dft_log2_max_size - 1),
+            dft_max_size: Some(2 << (dft_log2_max_size - 1)),
             ..self
         }
     }
 }
+// Segment size `l = trunc(n/(k(1-a)+a))` of `k` segments overlapping by a fraction `a` of a signal of length `n`
+fn segment_size(n: usize, k: usize, a: f64) -> usize {
+    (n as f64 / (k as f64 * (1. - a) + a)).trunc() as usize
+}
+// Number of segments `k=trunc((n-la)/(l(1-a)))` of size `l` overlapping by a fraction `a` of a signal of length `n`
+fn n_segment(n: usize, l: usize, a: f64) -> usize {
+    ((n as f64 - l as f64 * a) / (l as f64 * (1. - a))).trunc() as usize
+}
@@ -0,0 +1,62 @@
+use crate::Signal;
+use num_complex::Complex;
+
+/// Calibration of the signal, the magnitude of a frequency response
+///
+/// The frequency response `H(f)` converts the signal unit into a physical unit, e.g. `(m/s²)/V` for an accelerometer,
+/// and the periodogram is multiplied by `|H(f)|²` at each frequency bin (see [Builder::calibration](crate::Builder::calibration)).
+/// The magnitude of the frequency response is linearly interpolated between the calibration frequencies,
+/// and it is set to the magnitude at the first or at the last calibration frequency beyond the calibration frequencies.
+/// The magnitude at a negative frequency `-f` is the magnitude at `f`, unless the calibration frequencies are negative.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration<T: Signal> {
+    frequency: Vec<T>,
+    magnitude: Vec<T>,
+}
+impl<T: Signal> Calibration<T> {
+    /// Creates a calibration from the complex frequency `response` at the `frequency` `[Hz]` points
+    ///
+    /// Panics if the frequencies are not increasing, or if the frequencies and the response have different lengths
+    pub fn from_response(frequency: &[T], response: &[Complex<T>]) -> Self {
+        Self::from_magnitude(
+            frequency,
+            &response.iter().map(|h| h.norm()).collect::<Vec<T>>(),
+        )
+    }
+    /// Creates a calibration from the `magnitude` of the frequency response at the `frequency` `[Hz]` points
+    ///
+    /// Panics if the frequencies are not increasing, or if the frequencies and the magnitude have different lengths
+    pub fn from_magnitude(frequency: &[T], magnitude: &[T]) -> Self {
+        assert_eq!(
+            frequency.len(),
+            magnitude.len(),
+            "the calibration frequencies and magnitudes must have the same length"
+        );
+        assert!(
+            !frequency.is_empty() && frequency.windows(2).all(|f| f[0] < f[1]),
+            "the calibration frequencies must be increasing"
+        );
+        Self {
+            frequency: frequency.to_vec(),
+            magnitude: magnitude.to_vec(),
+        }
+    }
+    /// Returns the magnitude of the frequency response at the frequency `f` `[Hz]`
+    pub fn magnitude(&self, f: T) -> T {
+        let (x, y) = (&self.frequency, &self.magnitude);
+        let f = if x[0] >= T::zero() { f.abs() } else { f };
+        let i = x.partition_point(|&x| x <= f);
+        if i == 0 {
+            y[0]
+        } else if i == x.len() {
+            y[i - 1]
+        } else {
+            let w = (f - x[i - 1]) / (x[i] - x[i - 1]);
+            y[i - 1] + (y[i] - y[i - 1]) * w
+        }
+    }
+    /// Returns the power gain `|H(f)|²` of the frequency response at the frequency `f` `[Hz]`
+    pub fn gain(&self, f: T) -> T {
+        self.magnitude(f).powi(2)
+    }
+}
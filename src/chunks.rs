@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+
+// Signal samples, either contiguous or split into chunks
+//
+// A chunked signal is segmented across the chunks boundaries: the segments within a chunk are borrowed
+// from the chunk and only the segments that span a boundary are copied
+//
+// The length of a signal without samples is given to the builder of a plan, that only depends on the signal length
+#[derive(Debug, Clone)]
+pub(crate) enum Samples<'a, S: Clone> {
+    Contiguous(Cow<'a, [S]>),
+    Chunked { chunks: &'a [&'a [S]], len: usize },
+    Length(usize),
+}
+impl<'a, S: Clone> From<&'a [S]> for Samples<'a, S> {
+    fn from(signal: &'a [S]) -> Self {
+        Samples::Contiguous(Cow::Borrowed(signal))
+    }
+}
+impl<'a, S: Clone> From<Vec<S>> for Samples<'a, S> {
+    fn from(signal: Vec<S>) -> Self {
+        Samples::Contiguous(Cow::Owned(signal))
+    }
+}
+impl<'a, S: Clone> Samples<'a, S> {
+    pub(crate) fn chunked(chunks: &'a [&'a [S]]) -> Self {
+        Samples::Chunked {
+            chunks,
+            len: chunks.iter().map(|c| c.len()).sum(),
+        }
+    }
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Samples::Contiguous(signal) => signal.len(),
+            Samples::Chunked { len, .. } | Samples::Length(len) => *len,
+        }
+    }
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    // `true` if the signal has samples, and not only a length
+    pub(crate) fn has_samples(&self) -> bool {
+        !matches!(self, Samples::Length(_))
+    }
+    // Iterator over the samples of all the chunks, empty for a signal without samples
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &S> {
+        let (signal, chunks): (&[S], &[&[S]]) = match self {
+            Samples::Contiguous(signal) => (signal, &[]),
+            Samples::Chunked { chunks, .. } => (&[], chunks),
+            Samples::Length(_) => (&[], &[]),
+        };
+        signal.iter().chain(chunks.iter().flat_map(|c| c.iter()))
+    }
+    // The signal as a single slice, the chunks being concatenated, empty for a signal without samples
+    pub(crate) fn contiguous(&self) -> Cow<'_, [S]> {
+        match self {
+            Samples::Contiguous(signal) => Cow::Borrowed(signal),
+            Samples::Chunked { chunks, .. } => Cow::Owned(chunks.concat()),
+            Samples::Length(_) => Cow::Owned(Vec::new()),
+        }
+    }
+    // Segments of `size` samples starting every `step` samples, none for a signal without samples
+    pub(crate) fn segments(&self, size: usize, step: usize) -> impl Iterator<Item = Cow<'_, [S]>> {
+        let n = if self.has_samples() { self.len() } else { 0 };
+        // index of the chunk of the current segment and index of the first sample of the chunk
+        let (mut chunk, mut offset) = (0, 0);
+        (0..)
+            .map(move |j| j * step)
+            .take_while(move |&start| size > 0 && start + size <= n)
+            .map(move |start| match self {
+                Samples::Contiguous(signal) => Cow::Borrowed(&signal[start..start + size]),
+                Samples::Chunked { chunks, .. } => {
+                    while start >= offset + chunks[chunk].len() {
+                        offset += chunks[chunk].len();
+                        chunk += 1;
+                    }
+                    let i = start - offset;
+                    if i + size <= chunks[chunk].len() {
+                        Cow::Borrowed(&chunks[chunk][i..i + size])
+                    } else {
+                        Cow::Owned(
+                            chunks[chunk..]
+                                .iter()
+                                .flat_map(|c| c.iter())
+                                .skip(i)
+                                .take(size)
+                                .cloned()
+                                .collect(),
+                        )
+                    }
+                }
+                Samples::Length(_) => unreachable!("a signal without samples has no segment"),
+            })
+    }
+}
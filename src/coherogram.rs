@@ -0,0 +1,116 @@
+use crate::{
+    CrossSpectralDensity, Hann, Periodogram, Signal, TransferFunction, Waterfall, WelchError,
+    WelchPlan, Window,
+};
+
+/// Coherogram, the time-resolved coherence of 2 signals
+///
+/// The signals `x` and `y` are divided into blocks of the same size, either consecutive or overlapping,
+/// and the magnitude squared coherence of the signals is estimated within each block from the [CrossSpectralDensity]
+/// of the blocks, the [WelchPlan] being shared by all the blocks of both signals.
+/// The size of the blocks is the length of the signal the plan is built with,
+/// and the segmentation of the plan applies to each block, as for the [Waterfall] of a signal.
+///
+/// The coherence of a block is estimated from the segments of the block, such as the number of segments
+/// per block sets the bias and the variance of the coherence estimate.
+#[derive(Debug, Clone)]
+pub struct Coherogram<'a, T: Signal, W: Window<T> = Hann<T>> {
+    x: Waterfall<'a, T, W>,
+    y: &'a [T],
+}
+impl<'a, T: Signal, W: Window<T>> Coherogram<'a, T, W> {
+    /// Creates the coherogram of the signals `x` and `y` with consecutive blocks of the size of the signal the `plan` is built with
+    ///
+    /// Panics if the signals have different lengths or if the plan has a validity mask (see [Waterfall::new])
+    pub fn new(plan: WelchPlan<T, W>, x: &'a [T], y: &'a [T]) -> Self {
+        assert_eq!(
+            x.len(),
+            y.len(),
+            "the coherogram signals must have the same length"
+        );
+        Self {
+            x: Waterfall::new(plan, x),
+            y,
+        }
+    }
+    /// Sets the number of samples between the starting points of 2 consecutive blocks
+    ///
+    /// The blocks overlap if `block_step` is less than the block size
+    pub fn block_step(self, block_step: usize) -> Self {
+        Self {
+            x: self.x.block_step(block_step),
+            ..self
+        }
+    }
+    /// Returns the [WelchPlan] of the blocks
+    pub fn plan(&self) -> &WelchPlan<T, W> {
+        self.x.plan()
+    }
+    /// Returns the size of the blocks
+    pub fn block_size(&self) -> usize {
+        self.x.block_size()
+    }
+    /// Returns the number of blocks
+    pub fn n_block(&self) -> usize {
+        self.x.n_block()
+    }
+    /// Returns an iterator over the [CrossSpectralDensity] of each block
+    ///
+    /// The estimator of a block is an error if the block of either signal is rejected by [WelchPlan::estimate],
+    /// e.g. if it has NaN samples with [NanPolicy::Error](crate::NanPolicy::Error)
+    pub fn blocks(
+        &self,
+    ) -> impl Iterator<Item = Result<CrossSpectralDensity<'a, T, W>, WelchError>> + '_ {
+        let y = self.y;
+        self.x
+            .blocks()
+            .zip(self.x.block_ranges())
+            .map(move |(x, range)| CrossSpectralDensity::new(x?, self.plan().estimate(&y[range])?))
+    }
+    /// Returns the magnitude squared coherence of each block, `|Pxy|^2/(Pxx Pyy)` (see [TransferFunction::coherence])
+    ///
+    /// The coherences are ordered in time, such as they form a time × frequency array.
+    /// Returns the error of the first block rejected by [WelchPlan::estimate]
+    pub fn coherence(&self) -> Result<Vec<Periodogram<T>>, WelchError> {
+        self.blocks()
+            .map(|csd| csd.map(|csd| TransferFunction(csd).coherence()))
+            .collect()
+    }
+    /// Returns the time in seconds at the center of each block
+    ///
+    /// The time is relative to the first sample of the signals
+    pub fn time(&self) -> Vec<T> {
+        self.x.time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Coherogram, Hann, NanPolicy, WelchError, WelchPlan};
+
+    #[test]
+    fn nan_block() {
+        let x: Vec<f64> = (0..4_000)
+            .map(|i| (i as f64 * 0.1).sin() + ((i * 7_919 % 1_009) as f64 / 1_009. - 0.5))
+            .collect();
+        let plan: WelchPlan<f64, Hann<f64>> = WelchPlan::builder(1_000, 1.)
+            .n_segment(8)
+            .nan_policy(NanPolicy::Error)
+            .build();
+        let coherence = Coherogram::new(plan.clone(), &x, &x).coherence().unwrap();
+        assert_eq!(coherence.len(), 4);
+        coherence
+            .iter()
+            .flat_map(|c| c.iter())
+            .for_each(|(_, c)| assert!((c - 1.).abs() < 1e-9));
+        let mut y = x.clone();
+        y[1_500] = f64::NAN;
+        let coherogram = Coherogram::new(plan, &x, &y);
+        let blocks: Vec<_> = coherogram.blocks().map(|b| b.is_ok()).collect();
+        assert_eq!(blocks, [true, false, true, true]);
+        assert!(matches!(
+            coherogram.coherence(),
+            Err(WelchError::NanSample(500))
+        ));
+    }
+}
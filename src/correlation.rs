@@ -0,0 +1,107 @@
+use crate::Signal;
+use num_complex::Complex;
+use num_traits::Zero;
+use rustfft::FftPlanner;
+
+/// Normalization of the correlation functions
+///
+/// The sum of the `n-|k|` products of the samples at lag `k` is divided either by the signal length `n`
+/// or by the number of products `n-|k|`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CorrelationBias {
+    /// biased estimate, divided by `n`, which is positive semi-definite and tapers off at large lags
+    #[default]
+    Biased,
+    /// unbiased estimate, divided by `n-|k|`, which variance increases with the lag
+    Unbiased,
+}
+impl CorrelationBias {
+    // Normalization factor of the sum of products at lag `k` of signals of length `n`
+    fn scale<T: Signal>(&self, n: usize, k: usize) -> T {
+        match self {
+            CorrelationBias::Biased => T::from_usize(n).unwrap().recip(),
+            CorrelationBias::Unbiased => T::from_usize(n - k).unwrap().recip(),
+        }
+    }
+}
+
+// Circular correlation of `x` with `y`, or with itself if `y` is `None`, zero-padded to the size `m`
+//
+// The correlation is the inverse discrete Fourier transform of the product of the complex conjugate of
+// the discrete Fourier transform of `x` with the discrete Fourier transform of `y`,
+// the negative lags `-k` being at the indices `m-k`
+fn circular_correlation<T: Signal>(x: &[T], y: Option<&[T]>, m: usize) -> Vec<T> {
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(m);
+    let dft = |s: &[T]| {
+        let mut buffer: Vec<Complex<T>> = s
+            .iter()
+            .map(|&x| Complex::new(x, T::zero()))
+            .chain(std::iter::repeat(Complex::zero()))
+            .take(m)
+            .collect();
+        fft.process(&mut buffer);
+        buffer
+    };
+    let mut buffer = dft(x);
+    match y {
+        Some(y) => buffer
+            .iter_mut()
+            .zip(dft(y))
+            .for_each(|(x, y)| *x = x.conj() * y),
+        None => buffer
+            .iter_mut()
+            .for_each(|x| *x = Complex::new(x.norm_sqr(), T::zero())),
+    }
+    planner.plan_fft_inverse(m).process(&mut buffer);
+    let u = T::from_usize(m).unwrap().recip();
+    buffer.into_iter().map(|x| x.re * u).collect()
+}
+
+/// Returns the cross-correlation of the signals `x` and `y` from lag `-L` to lag `L`, `L` being the `max_lag`
+///
+/// The cross-correlation at lag `k` is the sum of the products `x[i]y[i+k]`, normalized according to the [CorrelationBias],
+/// such as a positive lag corresponds to `y` lagging `x`, as for the phase of the [CrossSpectralDensity](crate::CrossSpectralDensity).
+/// It is computed with the discrete Fourier transform of the signals zero-padded to avoid the circular correlation.
+/// The maximum lag is limited to `n-1`, `n` being the length of the signals.
+///
+/// Panics if the signals are empty or if they have different lengths
+pub fn cross_correlation<T: Signal>(
+    x: &[T],
+    y: &[T],
+    max_lag: usize,
+    bias: CorrelationBias,
+) -> Vec<T> {
+    assert_eq!(x.len(), y.len(), "the signals must have the same length");
+    assert!(!x.is_empty(), "the signals must not be empty");
+    let n = x.len();
+    let l = max_lag.min(n - 1);
+    let m = (n + l).next_power_of_two();
+    let c = circular_correlation(x, Some(y), m);
+    (0..l)
+        .rev()
+        .map(|k| c[m - k - 1] * bias.scale(n, k + 1))
+        .chain((0..=l).map(|k| c[k] * bias.scale(n, k)))
+        .collect()
+}
+
+/// Returns the autocorrelation of the signal `x` from lag `0` to lag `L`, `L` being the `max_lag`
+///
+/// The autocorrelation at lag `k` is the sum of the products `x[i]x[i+k]`, normalized according to the [CorrelationBias],
+/// the autocorrelation at negative lags being symmetric.
+/// It is computed with the discrete Fourier transform of the signal zero-padded to avoid the circular correlation.
+/// The maximum lag is limited to `n-1`, `n` being the length of the signal.
+///
+/// Panics if the signal is empty
+pub fn autocorrelation<T: Signal>(x: &[T], max_lag: usize, bias: CorrelationBias) -> Vec<T> {
+    assert!(!x.is_empty(), "the signal must not be empty");
+    let n = x.len();
+    let l = max_lag.min(n - 1);
+    let m = (n + l).next_power_of_two();
+    circular_correlation(x, None, m)
+        .into_iter()
+        .take(l + 1)
+        .enumerate()
+        .map(|(k, c)| c * bias.scale(n, k))
+        .collect()
+}
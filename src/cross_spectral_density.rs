@@ -0,0 +1,190 @@
+use crate::{
+    periodogram::one_sided, Build, Builder, Hann, Periodogram, Signal, Welch, WelchError, Window,
+};
+use num_complex::Complex;
+use std::fmt::Display;
+
+/// Cross spectral density
+///
+/// Computes the cross spectral density of 2 signals `x` and `y` sampled at the same frequency,
+/// averaging over the segments the product of the discrete Fourier transform of `y` with the complex conjugate
+/// of the discrete Fourier transform of `x`.
+/// Both signals are segmented and windowed identically with the [Window] `W` ([Hann] by default).
+///
+/// The spectral densities are scaled according to the [Scaling](crate::Scaling) and the [WindowCorrection](crate::WindowCorrection)
+/// of the [Builder], and multiplied by the power gain of the [Calibration](crate::Calibration) of both signals.
+/// The periodograms of the segments are averaged with the arithmetic mean, the other [Averaging](crate::Averaging) methods
+/// and the amplitude scalings being rejected by [Builder::try_build].
+/// The segments with missing samples in either signal are skipped (see [NanPolicy::Skip](crate::NanPolicy::Skip)
+/// and [Builder::mask]), [Builder::try_build] returning [WelchError::NoValidSegment] if no segment is valid for both signals.
+#[derive(Debug, Clone)]
+pub struct CrossSpectralDensity<'a, T: Signal, W: Window<T> = Hann<T>> {
+    x: Welch<'a, T, W>,
+    y: Welch<'a, T, W>,
+}
+impl<'a, T: Signal, W: Window<T>> CrossSpectralDensity<'a, T, W> {
+    /// Returns [Welch] [Builder] given the signals `x` and `y` sampled at `fs`Hz
+    pub fn builder(x: &'a [T], y: &'a [T], fs: T) -> Builder<'a, T, W> {
+        Builder::new(x).sampling_frequency(fs).cross_signal(y)
+    }
+    // Creates the cross spectral density from the estimators of the signals `x` and `y`,
+    // restricted to the segments valid for both signals
+    //
+    // Returns an error if no segment is valid for both signals
+    pub(crate) fn new(mut x: Welch<'a, T, W>, mut y: Welch<'a, T, W>) -> Result<Self, WelchError> {
+        x.common_segments(&mut y);
+        if x.n_segment == 0 {
+            return Err(WelchError::NoValidSegment);
+        }
+        Ok(Self { x, y })
+    }
+    /// Returns the [Welch] estimator of the signal `x`
+    pub fn x(&self) -> &Welch<'a, T, W> {
+        &self.x
+    }
+    /// Returns the [Welch] estimator of the signal `y`
+    pub fn y(&self) -> &Welch<'a, T, W> {
+        &self.y
+    }
+    /// Returns the cross spectral density periodogram
+    pub fn periodogram(&self) -> Periodogram<T, Complex<T>> {
+        let (_, _, pxy) = self.spectra();
+        self.x.to_periodogram(pxy)
+    }
+    /// Returns the cross spectral density phase in radians
+    ///
+    /// The phase is the phase of `y` relative to `x`
+    pub fn phase(&self) -> Periodogram<T> {
+        self.periodogram().phase()
+    }
+    // Returns the auto spectral densities of `x` and `y` and the cross spectral density
+    pub(crate) fn spectra(&self) -> (Vec<T>, Vec<T>, Vec<Complex<T>>) {
+        let welch = &self.x;
+        let n = welch.n_bins();
+        let u = welch.scale(welch.scaling) / T::from_usize(welch.n_segment).unwrap();
+        let mut pxx = vec![T::zero(); n];
+        let mut pyy = vec![T::zero(); n];
+        let mut pxy = vec![Complex::new(T::zero(), T::zero()); n];
+        self.x.dfts().zip(self.y.dfts()).for_each(|(x, y)| {
+            x.iter().zip(y).take(n).enumerate().for_each(|(i, (x, y))| {
+                pxx[i] += x.norm_sqr();
+                pyy[i] += y.norm_sqr();
+                pxy[i] = pxy[i] + x.conj() * y;
+            })
+        });
+        if !welch.is_two_sided() {
+            one_sided(&mut pxx, welch.dft_size);
+            one_sided(&mut pyy, welch.dft_size);
+            one_sided(&mut pxy, welch.dft_size);
+        }
+        (
+            welch.calibrated(pxx.into_iter().map(|x| x * u).collect()),
+            welch.calibrated(pyy.into_iter().map(|x| x * u).collect()),
+            welch.calibrated(pxy.into_iter().map(|x| x * u).collect()),
+        )
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Build<CrossSpectralDensity<'a, T, W>> for Builder<'a, T, W> {
+    fn build(&self) -> CrossSpectralDensity<'a, T, W> {
+        let y = self
+            .cross_signal
+            .expect("the cross spectral density requires a second signal");
+        assert_eq!(
+            self.signal.len(),
+            y.len(),
+            "the cross spectral density signals must have the same length"
+        );
+        let x: Welch<'a, T, W> = self.build();
+        let y: Welch<'a, T, W> = Builder {
+            signal: y.into(),
+            ..self.clone()
+        }
+        .build();
+        CrossSpectralDensity::new(x, y).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Display for CrossSpectralDensity<'a, T, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.x.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Builder, CrossSpectralDensity, Hann, NanPolicy, Welch, WelchError};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    fn white_noise(n: usize) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(n as u64);
+        (0..n).map(|_| StandardNormal.sample(&mut rng)).collect()
+    }
+
+    #[test]
+    fn auto_spectral_density() {
+        let x = white_noise(10_000);
+        // the spectral density of x with itself is the spectral density of x
+        let csd: CrossSpectralDensity<f64> = CrossSpectralDensity::builder(&x, &x, 10.).build();
+        let welch: Welch<f64, Hann<f64>> = Builder::new(&x).sampling_frequency(10.).build();
+        let (pxy, pxx) = (csd.periodogram(), welch.periodogram());
+        assert_eq!(pxy.len(), pxx.len());
+        pxy.iter().zip(pxx.iter()).for_each(|((f, pxy), (_, pxx))| {
+            assert!(
+                (pxy.re - pxx).abs() < 1e-12 * pxx && pxy.im.abs() < 1e-12 * pxx,
+                "{f}Hz: {pxy} vs {pxx}"
+            )
+        });
+    }
+
+    #[test]
+    fn delay() {
+        // y is x delayed by d samples: Pxy=Pxx exp(-i 2pi f d/fs)
+        let (fs, d) = (10., 2);
+        let x = white_noise(100_000 + d);
+        let (x, y) = (&x[d..], &x[..x.len() - d]);
+        let csd: CrossSpectralDensity<f64> = CrossSpectralDensity::builder(x, y, fs).build();
+        let pxx = csd.x().periodogram();
+        csd.periodogram()
+            .iter()
+            .zip(pxx.iter())
+            .skip(1)
+            .filter(|((f, _), _)| *f < 0.2 * fs)
+            .for_each(|((f, pxy), (_, pxx))| {
+                let phase = -2. * std::f64::consts::PI * f * d as f64 / fs;
+                assert!(
+                    (pxy.arg() - phase).abs() < 5e-2,
+                    "{f}Hz: {} vs {phase}",
+                    pxy.arg()
+                );
+                assert!(
+                    (pxy.norm() - pxx).abs() < 1e-2 * pxx,
+                    "{f}Hz: {pxy} vs {pxx}"
+                );
+            });
+    }
+
+    #[test]
+    fn no_common_segment() {
+        let n = 4_000;
+        let x: Vec<f64> = (0..n)
+            .map(|i| if i < n / 2 { f64::NAN } else { 1. })
+            .collect();
+        let y: Vec<f64> = x.iter().rev().cloned().collect();
+        let csd: Result<CrossSpectralDensity<f64, Hann<f64>>, _> =
+            CrossSpectralDensity::builder(&x, &y, 1.)
+                .n_segment(8)
+                .nan_policy(NanPolicy::Skip)
+                .try_build();
+        assert!(matches!(csd, Err(WelchError::NoValidSegment)));
+        let welch = Builder::<f64, Hann<f64>>::new(&x)
+            .n_segment(8)
+            .nan_policy(NanPolicy::Skip)
+            .try_build::<Welch<f64, Hann<f64>>>()
+            .unwrap();
+        let nan = vec![f64::NAN; n];
+        assert!(matches!(
+            welch.with_signal(&nan),
+            Err(WelchError::NoValidSegment)
+        ));
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{Periodogram, Signal};
+use std::io::{self, Write};
+
+impl<T: Signal> Periodogram<T> {
+    /// Writes the periodogram to `writer` as comma-separated values
+    ///
+    /// The first line is the header `frequency,value` followed by one line per frequency bin with
+    /// the frequency in Hz and the periodogram value.
+    /// If a `reference` is given, the values are written in dB relative to the reference instead
+    /// (see [Periodogram::to_db]) and the header is `frequency,dB`.
+    /// The writer is not buffered, wrap it into a [BufWriter](std::io::BufWriter) when writing to a file.
+    pub fn to_csv<W: Write>(&self, mut writer: W, reference: Option<T>) -> io::Result<()> {
+        let (values, header) = match reference {
+            Some(reference) => (self.to_db(reference).to_vec(), "dB"),
+            None => (self.to_vec(), "value"),
+        };
+        writeln!(writer, "frequency,{}", header)?;
+        for (f, x) in self.frequency().into_iter().zip(values) {
+            writeln!(writer, "{},{}", f.to_f64().unwrap(), x.to_f64().unwrap())?;
+        }
+        writer.flush()
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{periodogram::one_sided, Averaging, Sample, Signal, Welch, WelchError, Window};
+use cudarc::{
+    cufft::{
+        result as cufft,
+        sys::{cufftDoubleComplex, cufftHandle, cufftType},
+    },
+    driver::{
+        CudaContext, CudaFunction, CudaSlice, CudaStream, DevicePtrMut, LaunchConfig, PushKernelArg,
+    },
+    nvrtc::compile_ptx,
+};
+use std::{fmt::Display, sync::Arc};
+
+// Maximum number of samples of the segments transferred to the GPU in a single batch
+const BATCH_SIZE: usize = 1 << 22;
+
+// Squared magnitude of the `n_segment` discrete Fourier transforms of `n_bin` frequencies each,
+// either summed over the segments or for each segment
+const KERNELS: &str = r#"
+extern "C" __global__ void accumulate(const double* dft, double* sum, int n_bin, int n_segment) {
+    int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i < n_bin) {
+        double s = sum[i];
+        for (int k = 0; k < n_segment; k++) {
+            const double* z = dft + 2 * (k * n_bin + i);
+            s += z[0] * z[0] + z[1] * z[1];
+        }
+        sum[i] = s;
+    }
+}
+extern "C" __global__ void power(const double* dft, double* p, int n_bin, int n_segment) {
+    int i = blockIdx.x * blockDim.x + threadIdx.x;
+    if (i < n_bin * n_segment) {
+        const double* z = dft + 2 * i;
+        p[i] = z[0] * z[0] + z[1] * z[1];
+    }
+}
+"#;
+
+// Panics with the CUDA error
+fn check<X, E: Display>(result: Result<X, E>) -> X {
+    result.unwrap_or_else(|e| panic!("CUDA backend failed: {}", e))
+}
+
+// CUDA GPU plan of the segments discrete Fourier transform and of the periodograms accumulation
+//
+// The plan is created once, when the estimator is built, and it is shared between clones.
+#[derive(Debug)]
+pub(crate) struct CudaPlan {
+    stream: Arc<CudaStream>,
+    accumulate: CudaFunction,
+    power: CudaFunction,
+}
+impl CudaPlan {
+    // Creates the plan on the CUDA device `ordinal`, compiling the accumulation kernels
+    pub(crate) fn new(ordinal: usize) -> Result<Self, WelchError> {
+        let error = |e: &dyn Display| {
+            WelchError::UnavailableBackend(format!("CUDA device #{}: {}", ordinal, e))
+        };
+        let context = CudaContext::new(ordinal).map_err(|e| error(&e))?;
+        let ptx = compile_ptx(KERNELS).map_err(|e| error(&e))?;
+        let module = context.load_module(ptx).map_err(|e| error(&e))?;
+        Ok(Self {
+            stream: context.default_stream(),
+            accumulate: module.load_function("accumulate").map_err(|e| error(&e))?,
+            power: module.load_function("power").map_err(|e| error(&e))?,
+        })
+    }
+    // Returns an error if the CUDA libraries are not found or if the CUDA device `ordinal` does not exist
+    pub(crate) fn check_device(ordinal: usize) -> Result<(), WelchError> {
+        let libraries = [
+            ("driver", unsafe { cudarc::driver::sys::is_culib_present() }),
+            ("cuFFT", unsafe { cudarc::cufft::sys::is_culib_present() }),
+            ("NVRTC", unsafe { cudarc::nvrtc::sys::is_culib_present() }),
+        ];
+        if let Some((name, _)) = libraries.iter().find(|(_, present)| !present) {
+            return Err(WelchError::UnavailableBackend(format!(
+                "CUDA {} library not found",
+                name
+            )));
+        }
+        let n = CudaContext::device_count()
+            .map_err(|e| WelchError::UnavailableBackend(format!("CUDA driver: {}", e)))?;
+        if ordinal < n.max(0) as usize {
+            Ok(())
+        } else {
+            Err(WelchError::UnavailableBackend(format!(
+                "CUDA device #{} not found among {} devices",
+                ordinal, n
+            )))
+        }
+    }
+    // Fourier transforms the `welch` segments by batches on the GPU
+    //
+    // `f` is called with the Fourier transform of each batch, with `n_bins` frequencies for each segment,
+    // and with the number of segments in the batch
+    fn batches<T, W, S, F>(&self, welch: &Welch<T, W, S>, mut f: F)
+    where
+        T: Signal,
+        W: Window<T>,
+        S: Sample<T>,
+        F: FnMut(&CudaSlice<f64>, usize),
+    {
+        let stream = &self.stream;
+        let m = welch.dft_size;
+        let n = welch.n_bins();
+        // real segments with a real-to-complex transform or complex segments with interleaved real and imaginary parts
+        let complex = welch.is_two_sided();
+        let width = if complex { 2 * m } else { m };
+        let batch = (BATCH_SIZE / width).clamp(1, welch.n_segment.max(1));
+        let mut input: CudaSlice<f64> = check(stream.alloc_zeros(batch * width));
+        let mut output: CudaSlice<f64> = check(stream.alloc_zeros(batch * 2 * n));
+        let mut plan: Option<(usize, cufftHandle)> = None;
+        let mut host = Vec::with_capacity(batch * width);
+        let mut segments = welch.windowed_segments().peekable();
+        while segments.peek().is_some() {
+            host.clear();
+            let mut b = 0;
+            for s in segments.by_ref().take(batch) {
+                let start = host.len();
+                if complex {
+                    host.extend(
+                        s.iter()
+                            .flat_map(|x| [x.re, x.im].map(|x| x.to_f64().unwrap())),
+                    );
+                } else {
+                    host.extend(s.iter().map(|x| x.re.to_f64().unwrap()));
+                }
+                host.resize(start + width, 0.);
+                b += 1;
+            }
+            check(stream.memcpy_htod(&host, &mut input));
+            let handle = match plan {
+                Some((size, handle)) if size == b => handle,
+                _ => {
+                    if let Some((_, handle)) = plan.take() {
+                        unsafe { check(cufft::destroy(handle)) };
+                    }
+                    let kind = if complex {
+                        cufftType::CUFFT_Z2Z
+                    } else {
+                        cufftType::CUFFT_D2Z
+                    };
+                    let handle = check(cufft::plan_1d(m as i32, kind, b as i32));
+                    unsafe { check(cufft::set_stream(handle, stream.cu_stream() as _)) };
+                    plan = Some((b, handle));
+                    handle
+                }
+            };
+            {
+                let (x, _x) = input.device_ptr_mut(stream);
+                let (y, _y) = output.device_ptr_mut(stream);
+                let y = y as *mut cufftDoubleComplex;
+                unsafe {
+                    if complex {
+                        check(cufft::exec_z2z(handle, x as *mut cufftDoubleComplex, y, -1));
+                    } else {
+                        check(cufft::exec_d2z(handle, x as *mut f64, y));
+                    }
+                }
+            }
+            f(&output, b);
+        }
+        if let Some((_, handle)) = plan {
+            unsafe { check(cufft::destroy(handle)) };
+        }
+    }
+    // Number of segments and sum of the squared magnitude of the segments discrete Fourier transform
+    fn power_sum<T: Signal, W: Window<T>, S: Sample<T>>(
+        &self,
+        welch: &Welch<T, W, S>,
+    ) -> (usize, Vec<f64>) {
+        let n = welch.n_bins();
+        let mut sum: CudaSlice<f64> = check(self.stream.alloc_zeros(n));
+        let mut k = 0;
+        self.batches(welch, |dft, b| {
+            let (n_bin, n_segment) = (n as i32, b as i32);
+            let mut launch = self.stream.launch_builder(&self.accumulate);
+            launch.arg(dft).arg(&mut sum).arg(&n_bin).arg(&n_segment);
+            unsafe { check(launch.launch(LaunchConfig::for_num_elems(n as u32))) };
+            k += b;
+        });
+        (k, check(self.stream.clone_dtoh(&sum)))
+    }
+    // Squared magnitude of each segment discrete Fourier transform
+    fn power<T: Signal, W: Window<T>, S: Sample<T>>(
+        &self,
+        welch: &Welch<T, W, S>,
+    ) -> Vec<Vec<f64>> {
+        let n = welch.n_bins();
+        let mut segments = Vec::with_capacity(welch.n_segment);
+        let mut p: Option<CudaSlice<f64>> = None;
+        self.batches(welch, |dft, b| {
+            let p = p.get_or_insert_with(|| check(self.stream.alloc_zeros(dft.len() / 2)));
+            let (n_bin, n_segment) = (n as i32, b as i32);
+            let mut launch = self.stream.launch_builder(&self.power);
+            launch.arg(dft).arg(&mut *p).arg(&n_bin).arg(&n_segment);
+            unsafe { check(launch.launch(LaunchConfig::for_num_elems((b * n) as u32))) };
+            let host = check(self.stream.clone_dtoh(&p.slice(..b * n)));
+            segments.extend(host.chunks(n).map(|x| x.to_vec()));
+        });
+        segments
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
+    // Converts the squared magnitude of a discrete Fourier transform computed on the GPU
+    //
+    // The positive frequencies of one-sided spectrum are doubled to account for the negative frequencies
+    fn cuda_power(&self, power: Vec<f64>, u: f64) -> Vec<T> {
+        let mut p: Vec<T> = power
+            .into_iter()
+            .map(|x| T::from_f64(x * u).unwrap())
+            .collect();
+        if !self.is_two_sided() {
+            one_sided(&mut p, self.dft_size);
+        }
+        p
+    }
+    // Average of the segments periodogram computed on the GPU
+    //
+    // The periodograms are accumulated on the GPU for the arithmetic mean,
+    // otherwise they are transferred back and averaged on the CPU
+    pub(crate) fn cuda_averaged_power(&self, cuda: &CudaPlan) -> Vec<T> {
+        if self.averaging == Averaging::Mean {
+            let (k, sum) = cuda.power_sum(self);
+            self.cuda_power(sum, (k.max(1) as f64).recip())
+        } else {
+            self.averaging
+                .average(self.cuda_segments_power(cuda).into_iter(), self.n_bins())
+        }
+    }
+    // Squared magnitude of each segment discrete Fourier transform computed on the GPU
+    pub(crate) fn cuda_segments_power(&self, cuda: &CudaPlan) -> Vec<Vec<T>> {
+        cuda.power(self)
+            .into_iter()
+            .map(|p| self.cuda_power(p, 1.))
+            .collect()
+    }
+}
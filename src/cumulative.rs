@@ -0,0 +1,33 @@
+use crate::{Periodogram, Signal};
+
+impl<T: Signal> Periodogram<T> {
+    /// Returns the cumulative power, the integral of the spectral density from the first frequency bin up to each frequency
+    ///
+    /// The cumulative power at the frequency `f_i` is the sum of `S(f_j) df` for `j<=i`,
+    /// `S` being the spectral density and `df` the width of the frequency bins ([Periodogram::df]),
+    /// such as the last value is the total power of the signal.
+    /// The first frequency bin of a one-sided periodogram is the zero frequency,
+    /// whereas it is the frequency `-fs/2` for a two-sided periodogram.
+    ///
+    /// The periodogram is assumed to be a spectral density ([Scaling::Density](crate::Scaling::Density)),
+    /// the cumulative power of a power spectrum being obtained by dividing with [Periodogram::df].
+    pub fn cumulative(&self) -> Periodogram<T> {
+        let df = self.df();
+        self.with_values(
+            self[..]
+                .iter()
+                .scan(T::zero(), |p, &x| {
+                    *p += x * df;
+                    Some(*p)
+                })
+                .collect(),
+        )
+    }
+    /// Returns the cumulative root-mean-square amplitude (signal unit), the square root of the cumulative power
+    ///
+    /// See [Periodogram::cumulative]; the last value is the root-mean-square amplitude of the signal.
+    pub fn cumulative_rms(&self) -> Periodogram<T> {
+        let p = self.cumulative();
+        p.with_values(p[..].iter().map(|&x| x.max(T::zero()).sqrt()).collect())
+    }
+}
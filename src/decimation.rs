@@ -0,0 +1,42 @@
+use crate::{Hamming, Sample, Signal, Window};
+use num_complex::Complex;
+use num_traits::Zero;
+
+// Decimates the `signal` by `factor` after filtering it with a low-pass FIR filter
+//
+// The filter is a symmetric Hamming windowed sinc of `20 factor+1` taps with the cutoff frequency at the decimated Nyquist frequency,
+// the filter delay is compensated and the signal is zero-padded beyond its edges
+pub(crate) fn decimate<T: Signal, S: Sample<T>>(signal: &[S], factor: usize) -> Vec<S> {
+    let half = 10 * factor;
+    let window: Hamming<T> = Hamming::new(2 * half + 1);
+    let pi = T::from_f64(std::f64::consts::PI).unwrap();
+    let d = T::from_usize(factor).unwrap();
+    let taps: Vec<T> = window
+        .weights()
+        .iter()
+        .enumerate()
+        .map(|(i, &w)| {
+            let x = pi * (T::from_usize(i).unwrap() - T::from_usize(half).unwrap()) / d;
+            let sinc = if x.is_zero() { T::one() } else { x.sin() / x };
+            w * sinc / d
+        })
+        .collect();
+    let n = signal.len();
+    (0..n)
+        .step_by(factor)
+        .map(|j| {
+            // y[j] = sum_k h[k] x[j+half-k]
+            let x = taps
+                .iter()
+                .enumerate()
+                .filter_map(|(k, &h)| {
+                    (j + half)
+                        .checked_sub(k)
+                        .filter(|&i| i < n)
+                        .map(|i| signal[i].to_complex() * h)
+                })
+                .fold(Complex::zero(), |a, x| a + x);
+            S::from_complex(x)
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+use crate::{Sample, Signal};
+use num_complex::Complex;
+use std::sync::Arc;
+
+// In place detrending closure
+type DetrendFn<T> = Arc<dyn Fn(&mut [T]) + Send + Sync>;
+
+/// Segment detrending
+///
+/// The trend is removed from each segment before the window is applied.
+/// The real and imaginary parts of complex signals are detrended separately.
+/// A custom detrending function is created with [Detrend::custom].
+#[derive(Clone, Default)]
+pub enum Detrend<T> {
+    /// no detrending
+    #[default]
+    None,
+    /// removes the segment mean
+    Constant,
+    /// removes the least-squares straight line fitted to the segment
+    Linear,
+    /// removes the least-squares polynomial of the given order fitted to the segment
+    Polynomial(usize),
+    /// detrends the segment in place with a closure
+    Custom(DetrendFn<T>),
+}
+impl<T> std::fmt::Debug for Detrend<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Detrend::None => write!(f, "None"),
+            Detrend::Constant => write!(f, "Constant"),
+            Detrend::Linear => write!(f, "Linear"),
+            Detrend::Polynomial(order) => write!(f, "Polynomial({})", order),
+            Detrend::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+impl<T: Signal> Detrend<T> {
+    /// Creates a custom detrending from a closure `f` that detrends a segment in place
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&mut [T]) + Send + Sync + 'static,
+    {
+        Detrend::Custom(Arc::new(f))
+    }
+    // Returns `true` if the segments are detrended
+    pub(crate) fn is_some(&self) -> bool {
+        !matches!(self, Detrend::None)
+    }
+    // Returns the detrended `segment`
+    pub(crate) fn apply<S: Sample<T>>(&self, segment: &[S]) -> Vec<Complex<T>> {
+        let mut re: Vec<T> = segment.iter().map(|x| x.to_complex().re).collect();
+        let mut im: Vec<T> = segment.iter().map(|x| x.to_complex().im).collect();
+        self.detrend(&mut re);
+        if S::COMPLEX {
+            self.detrend(&mut im);
+        }
+        re.into_iter()
+            .zip(im)
+            .map(|(re, im)| Complex::new(re, im))
+            .collect()
+    }
+    // Removes the trend from the real `data`
+    pub(crate) fn detrend(&self, data: &mut [T]) {
+        match self {
+            Detrend::None => (),
+            Detrend::Constant => {
+                let mean = data.iter().cloned().sum::<T>() / T::from_usize(data.len()).unwrap();
+                data.iter_mut().for_each(|x| *x -= mean);
+            }
+            Detrend::Linear => {
+                // x - mean(x) - b (t - mean(t)) with b = sum((t-mean(t)) x)/sum((t-mean(t))^2)
+                let n = T::from_usize(data.len()).unwrap();
+                let mean = data.iter().cloned().sum::<T>() / n;
+                let t_mean = (n - T::one()) * T::from_f64(0.5).unwrap();
+                let (tx, tt) =
+                    data.iter()
+                        .enumerate()
+                        .fold((T::zero(), T::zero()), |(tx, tt), (i, &x)| {
+                            let t = T::from_usize(i).unwrap() - t_mean;
+                            (tx + t * x, tt + t * t)
+                        });
+                let b = if tt > T::zero() { tx / tt } else { T::zero() };
+                data.iter_mut().enumerate().for_each(|(i, x)| {
+                    *x -= mean + b * (T::from_usize(i).unwrap() - t_mean);
+                });
+            }
+            Detrend::Polynomial(order) => polynomial_detrend(data, *order),
+            Detrend::Custom(f) => f(data),
+        }
+    }
+}
+// Removes the least-squares polynomial of order `order` from `data`
+//
+// The data is projected onto an orthonormal basis of polynomials of the abscissa scaled to [-1,1],
+// built with the modified Gram-Schmidt process
+fn polynomial_detrend<T: Signal>(data: &mut [T], order: usize) {
+    let n = data.len();
+    if n < 2 {
+        data.iter_mut().for_each(|x| *x = T::zero());
+        return;
+    }
+    let u: Vec<T> = (0..n)
+        .map(|i| T::from_usize(2 * i).unwrap() / T::from_usize(n - 1).unwrap() - T::one())
+        .collect();
+    let dot = |a: &[T], b: &[T]| a.iter().zip(b).map(|(&a, &b)| a * b).sum::<T>();
+    let mut basis: Vec<Vec<T>> = Vec::with_capacity(order + 1);
+    let mut p = vec![T::one(); n];
+    for _ in 0..=order.min(n - 1) {
+        for q in &basis {
+            let c = dot(&p, q);
+            p.iter_mut().zip(q).for_each(|(p, &q)| *p -= c * q);
+        }
+        let norm = dot(&p, &p).sqrt();
+        if norm > T::zero() {
+            p.iter_mut().for_each(|p| *p = *p / norm);
+        }
+        let c = dot(data, &p);
+        data.iter_mut().zip(&p).for_each(|(x, &p)| *x -= c * p);
+        let next = p.iter().zip(&u).map(|(&p, &u)| p * u).collect();
+        basis.push(p);
+        p = next;
+    }
+}
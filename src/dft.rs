@@ -0,0 +1,78 @@
+use crate::{simd::Vectorized, Sample, Signal};
+use num_complex::Complex;
+use num_traits::Zero;
+use realfft::{RealFftPlanner, RealToComplex};
+use rustfft::{Fft, FftPlanner};
+use std::{fmt::Debug, sync::Arc};
+
+// Discrete Fourier transform plan
+//
+// Complex signals, or real signals when the two-sided spectrum is required, are Fourier transformed
+// with a complex-to-complex transform and real signals with a real-to-complex transform
+// that returns only the `m/2+1` non-negative frequencies.
+// The plan is shared between clones.
+#[derive(Clone)]
+pub(crate) enum DftPlan<T: Signal> {
+    Complex(Arc<dyn Fft<T>>),
+    Real(Arc<dyn RealToComplex<T>>),
+}
+impl<T: Signal> Debug for DftPlan<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DftPlan::Complex(fft) => write!(f, "DftPlan::Complex({})", fft.len()),
+            DftPlan::Real(fft) => write!(f, "DftPlan::Real({})", fft.len()),
+        }
+    }
+}
+impl<T: Signal> DftPlan<T> {
+    // Creates a new plan for a complex or real discrete Fourier transform of size `m`
+    pub(crate) fn new(m: usize, complex: bool) -> Self {
+        if complex {
+            DftPlan::Complex(FftPlanner::new().plan_fft_forward(m))
+        } else {
+            DftPlan::Real(RealFftPlanner::new().plan_fft_forward(m))
+        }
+    }
+    // Returns a scratch buffer for [DftPlan::process]
+    pub(crate) fn make_scratch_vec(&self) -> Vec<Complex<T>> {
+        match self {
+            DftPlan::Complex(fft) => vec![Complex::zero(); fft.get_inplace_scratch_len()],
+            DftPlan::Real(fft) => fft.make_scratch_vec(),
+        }
+    }
+    // Fourier transform of the `samples` windowed with `weights` and zero-padded to the transform size
+    pub(crate) fn process<S: Sample<T>>(
+        &self,
+        samples: &[S],
+        weights: &[T],
+        scratch: &mut [Complex<T>],
+    ) -> Vec<Complex<T>> {
+        match self {
+            DftPlan::Complex(fft) => {
+                let mut buffer: Vec<Complex<T>> = vec![Complex::zero(); fft.len()];
+                samples
+                    .iter()
+                    .zip(weights)
+                    .zip(&mut buffer)
+                    .for_each(|((&x, &w), c)| *c = x.to_complex() * w);
+                fft.process_with_scratch(&mut buffer, scratch);
+                buffer
+            }
+            DftPlan::Real(fft) => {
+                let mut buffer = fft.make_input_vec();
+                match S::as_real(samples) {
+                    Some(x) => T::windowed(x, weights, &mut buffer),
+                    None => samples
+                        .iter()
+                        .zip(weights)
+                        .zip(&mut buffer)
+                        .for_each(|((&x, &w), c)| *c = x.to_complex().re * w),
+                }
+                let mut dft = fft.make_output_vec();
+                fft.process_with_scratch(&mut buffer, &mut dft, scratch)
+                    .expect("real-to-complex Fourier transform failed");
+                dft
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{Periodogram, Sample, Scaling, Signal, Welch, Window};
+use num_complex::Complex;
+use num_traits::Zero;
+use rustfft::FftPlanner;
+
+// Fraction of the power of a tone leaking outside of the bins the tone power is summed over
+const LEAKAGE: f64 = 1e-10;
+// Largest half-width of the band of a tone in equivalent noise bandwidths of the window
+const MAX_ENBW: f64 = 8.;
+
+/// Harmonic distortion of a tone
+///
+/// The power of the fundamental tone, of its harmonics and of the remaining noise,
+/// integrated over the frequency bins of a one-sided spectral density.
+/// The power of the zero frequency is excluded from the noise.
+///
+/// The data converter metrics ([SNR](Distortion::snr), [SINAD](Distortion::sinad),
+/// [SFDR](Distortion::sfdr) and [ENOB](Distortion::enob)) are derived from the same powers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distortion<T> {
+    /// frequency of the fundamental tone `[Hz]`
+    pub fundamental: T,
+    /// power of the fundamental tone
+    pub fundamental_power: T,
+    /// power of the harmonics, starting with the 2nd harmonic
+    pub harmonics_power: Vec<T>,
+    /// power of the noise, excluding the zero frequency, the fundamental tone and its harmonics
+    pub noise_power: T,
+    /// power of the largest spurious tone, either a harmonic or the largest peak of the noise
+    pub spur_power: T,
+}
+impl<T: Signal> Distortion<T> {
+    /// Returns the total harmonic distortion, the ratio of the harmonics to the fundamental RMS amplitudes
+    pub fn thd(&self) -> T {
+        (self.harmonics_power.iter().cloned().sum::<T>() / self.fundamental_power).sqrt()
+    }
+    /// Returns the total harmonic distortion plus noise, the ratio of the harmonics and noise to the fundamental RMS amplitudes
+    pub fn thd_n(&self) -> T {
+        ((self.harmonics_power.iter().cloned().sum::<T>() + self.noise_power)
+            / self.fundamental_power)
+            .sqrt()
+    }
+    /// Returns the signal-to-noise ratio in dB, excluding the harmonics
+    pub fn snr(&self) -> T {
+        db(self.fundamental_power / self.noise_power)
+    }
+    /// Returns the signal-to-noise and distortion ratio in dB
+    pub fn sinad(&self) -> T {
+        db(self.fundamental_power
+            / (self.harmonics_power.iter().cloned().sum::<T>() + self.noise_power))
+    }
+    /// Returns the spurious-free dynamic range in dB, the ratio of the fundamental to the largest spurious tone powers
+    pub fn sfdr(&self) -> T {
+        db(self.fundamental_power / self.spur_power)
+    }
+    /// Returns the effective number of bits `(SINAD-1.76)/6.02` of a data converter digitizing a full scale tone
+    pub fn enob(&self) -> T {
+        (self.sinad() - T::from_f64(1.76).unwrap()) / T::from_f64(6.02).unwrap()
+    }
+}
+// Power ratio in decibels
+fn db<T: Signal>(x: T) -> T {
+    T::from_f64(10.).unwrap() * x.log10()
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the [Distortion] of the tone at the frequency `fundamental` `[Hz]` with `n_harmonics` harmonics
+    ///
+    /// The periodogram must be a one-sided spectral density.
+    /// The power of the tone and of each harmonic is summed over the bins within `half_width` bins of
+    /// their frequency, to account for the leakage of the window.
+    /// The bins of the fundamental are excluded from the zero frequency band, and the zero frequency bin from the tones.
+    /// If `fundamental` is `None`, the tone is the largest value of the periodogram outside of the zero frequency bins.
+    /// The harmonics above the Nyquist frequency are ignored.
+    pub fn distortion(
+        &self,
+        fundamental: Option<T>,
+        n_harmonics: usize,
+        half_width: usize,
+    ) -> Distortion<T> {
+        assert!(
+            !self.is_two_sided() && self.f0().is_zero(),
+            "the distortion requires a one-sided periodogram from the zero frequency"
+        );
+        let n = self.len();
+        let df = self.df();
+        let i0 = fundamental.map_or_else(
+            || {
+                (half_width + 1..n)
+                    .max_by(|&i, &j| self[i].partial_cmp(&self[j]).unwrap())
+                    .expect("the periodogram is too short")
+            },
+            |f| (f / df).round().to_usize().unwrap(),
+        );
+        // sums the power of the bins around `i` not already summed
+        let band_power = |used: &mut [bool], i: usize| {
+            (i.saturating_sub(half_width)..(i + half_width + 1).min(n))
+                .filter(|&j| !std::mem::replace(&mut used[j], true))
+                .map(|j| self[j])
+                .sum::<T>()
+                * df
+        };
+        // the zero frequency bin is never part of a tone, and the fundamental is summed before the zero frequency band
+        let mut used = vec![false; n];
+        used[0] = true;
+        let fundamental_power = band_power(&mut used, i0);
+        used[0] = false;
+        band_power(&mut used, 0);
+        let harmonics_power: Vec<T> = (2..n_harmonics + 2)
+            .map(|k| k * i0)
+            .take_while(|&i| i < n)
+            .map(|i| band_power(&mut used, i))
+            .collect();
+        let noise_power = self
+            .iter()
+            .zip(&used)
+            .filter(|(_, &used)| !used)
+            .map(|((_, p), _)| p)
+            .sum::<T>()
+            * df;
+        let noise_spur_power = (0..n)
+            .filter(|&i| !used[i])
+            .max_by(|&i, &j| self[i].partial_cmp(&self[j]).unwrap())
+            .map_or_else(T::zero, |i| band_power(&mut used, i));
+        let spur_power = harmonics_power
+            .iter()
+            .cloned()
+            .fold(noise_spur_power, T::max);
+        Distortion {
+            fundamental: T::from_usize(i0).unwrap() * df,
+            fundamental_power,
+            harmonics_power,
+            noise_power,
+            spur_power,
+        }
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
+    /// Returns the [Distortion] of the tone at the frequency `fundamental` `[Hz]` with `n_harmonics` harmonics
+    ///
+    /// The power of the tone and of each harmonic is summed over the smallest number of bins
+    /// that holds all but a fraction `1e-10` of the power of a tone located halfway between 2 bins,
+    /// according to the spectrum of the window (see [Periodogram::distortion]).
+    /// The half-width of the band is limited to 8 equivalent noise bandwidths of the window ([Welch::enbw]),
+    /// for the windows with slowly decaying sidelobes, like [Hamming](crate::Hamming) or [One](crate::One),
+    /// that never reach the leakage target.
+    /// A window with low sidelobes, like [BlackmanHarris](crate::BlackmanHarris), keeps the number of bins small.
+    pub fn distortion(&self, fundamental: Option<T>, n_harmonics: usize) -> Distortion<T> {
+        self.scaled_periodogram(Scaling::Density).distortion(
+            fundamental,
+            n_harmonics,
+            self.leakage_half_width(),
+        )
+    }
+    // Half-width in bins of the band around a tone located halfway between 2 bins
+    // that holds all the tone power but a fraction [LEAKAGE], at most [MAX_ENBW] equivalent noise bandwidths
+    fn leakage_half_width(&self) -> usize {
+        let m = self.dft_size;
+        let max_width = (T::from_f64(MAX_ENBW).unwrap() * self.enbw() * T::from_usize(m).unwrap()
+            / T::from_usize(self.segment_size).unwrap())
+        .ceil()
+        .to_usize()
+        .unwrap_or(m / 2)
+        .min(m / 2);
+        let pi = T::from_f64(std::f64::consts::PI).unwrap();
+        let mut buffer = vec![Complex::zero(); m];
+        self.window
+            .weights()
+            .iter()
+            .zip(&mut buffer)
+            .enumerate()
+            .for_each(|(i, (&w, c))| {
+                *c = Complex::from_polar(
+                    w,
+                    pi * T::from_usize(i).unwrap() / T::from_usize(m).unwrap(),
+                )
+            });
+        FftPlanner::new().plan_fft_forward(m).process(&mut buffer);
+        let p: Vec<T> = buffer.iter().map(|x| x.norm_sqr()).collect();
+        let power = p.iter().cloned().sum::<T>() * (T::one() - T::from_f64(LEAKAGE).unwrap());
+        let mut band_power = p[0];
+        let mut h = 0;
+        while band_power < power && h < max_width {
+            h += 1;
+            band_power = band_power + p[h] + p[m - h];
+        }
+        h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Builder, Hamming, Hann, One, Welch, Window};
+
+    // THD of a tone with a 1% second harmonic
+    fn thd<W: Window<f64>>(f: f64) -> f64 {
+        let fs = 48e3;
+        let signal: Vec<f64> = (0..48_000)
+            .map(|i| {
+                let w = 2. * std::f64::consts::PI * f * i as f64 / fs;
+                w.sin() + 0.01 * (2. * w).sin()
+            })
+            .collect();
+        let welch: Welch<f64, W> = Builder::new(&signal)
+            .sampling_frequency(fs)
+            .segment_size(4800)
+            .power_of_two_dft(false)
+            .build();
+        welch.distortion(Some(f), 3).thd()
+    }
+
+    #[test]
+    fn thd_bin_centered() {
+        for thd in [
+            thd::<Hann<f64>>(1e3),
+            thd::<Hamming<f64>>(1e3),
+            thd::<One<f64>>(1e3),
+        ] {
+            assert!((thd - 0.01).abs() < 1e-4, "THD: {thd}");
+        }
+    }
+
+    #[test]
+    fn thd_between_bins() {
+        for thd in [thd::<Hann<f64>>(1005.), thd::<Hamming<f64>>(1005.)] {
+            assert!((thd - 0.01).abs() < 1e-3, "THD: {thd}");
+        }
+    }
+}
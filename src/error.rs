@@ -0,0 +1,217 @@
+use std::{error::Error, fmt::Display};
+
+/// [Builder](crate::Builder) settings errors
+///
+/// The errors are returned by [Builder::try_build](crate::Builder::try_build)
+/// and [Builder::validate](crate::Builder::validate)
+#[derive(Debug, Clone, PartialEq)]
+pub enum WelchError {
+    /// the signal is empty
+    EmptySignal,
+    /// the second signal length differs from the signal length
+    CrossSignalLength {
+        signal_len: usize,
+        cross_signal_len: usize,
+    },
+    /// the sampling frequency is not strictly positive
+    InvalidSamplingFrequency(f64),
+    /// the segment overlapping fraction is not within `[0,1)`
+    InvalidOverlap(f64),
+    /// the overlap is not less than the segment size
+    InvalidOverlapSize {
+        overlap_size: usize,
+        segment_size: usize,
+    },
+    /// the segment size is not within `[1,n]`
+    InvalidSegmentSize {
+        segment_size: usize,
+        signal_len: usize,
+    },
+    /// the signal is too short for a single segment
+    NoSegment,
+    /// the size of the discrete Fourier transform is less than the segment size
+    InvalidDftSize {
+        dft_size: usize,
+        segment_size: usize,
+    },
+    /// the fraction of the trimmed or winsorized mean is not within `[0,0.5)`
+    InvalidAveraging(f64),
+    /// the decimation factor is zero
+    InvalidDecimation,
+    /// the computation backend cannot be initialized
+    UnavailableBackend(String),
+    /// the signal has a NaN sample at the given index
+    NanSample(usize),
+    /// the validity mask length differs from the signal length
+    MaskLength { mask_len: usize, signal_len: usize },
+    /// all the segments have missing samples
+    NoValidSegment,
+    /// the size of a window that cannot be resized differs from the segment size
+    WindowSize {
+        window_size: usize,
+        segment_size: usize,
+    },
+    /// the record length differs from the length of the records of the [WelchPlan](crate::WelchPlan)
+    RecordLength { record_len: usize, plan_len: usize },
+    /// the setting is not supported by the cross spectral estimates
+    UnsupportedCrossSetting(&'static str),
+    /// the segment size is less than the smallest size of the window, e.g. the order of a DPSS window plus 1
+    WindowMinSize {
+        min_size: usize,
+        segment_size: usize,
+    },
+    /// the setting is not supported by the streaming estimator
+    UnsupportedStreamingSetting(&'static str),
+}
+impl Display for WelchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WelchError::EmptySignal => write!(f, "the signal is empty"),
+            WelchError::CrossSignalLength {
+                signal_len,
+                cross_signal_len,
+            } => write!(
+                f,
+                "the signals must have the same length ({} and {})",
+                signal_len, cross_signal_len
+            ),
+            WelchError::InvalidSamplingFrequency(fs) => {
+                write!(f, "the sampling frequency ({}) must be positive", fs)
+            }
+            WelchError::InvalidOverlap(a) => write!(
+                f,
+                "the segment overlapping fraction ({}) must be within [0,1)",
+                a
+            ),
+            WelchError::InvalidOverlapSize {
+                overlap_size,
+                segment_size,
+            } => write!(
+                f,
+                "the overlap size ({}) must be less than the segment size ({})",
+                overlap_size, segment_size
+            ),
+            WelchError::InvalidSegmentSize {
+                segment_size,
+                signal_len,
+            } => write!(
+                f,
+                "the segment size ({}) must be within [1,{}]",
+                segment_size, signal_len
+            ),
+            WelchError::NoSegment => write!(f, "the signal is too short for a single segment"),
+            WelchError::InvalidDftSize {
+                dft_size,
+                segment_size,
+            } => write!(
+                f,
+                "the DFT size ({}) must be greater or equal to the segment size ({})",
+                dft_size, segment_size
+            ),
+            WelchError::InvalidAveraging(x) => {
+                write!(f, "the averaging fraction ({}) must be within [0,0.5)", x)
+            }
+            WelchError::InvalidDecimation => write!(f, "the decimation factor must be positive"),
+            WelchError::UnavailableBackend(e) => {
+                write!(f, "the computation backend is not available ({})", e)
+            }
+            WelchError::NanSample(i) => write!(f, "the signal sample #{} is NaN", i),
+            WelchError::MaskLength {
+                mask_len,
+                signal_len,
+            } => write!(
+                f,
+                "the validity mask must have the same length than the signal ({} and {})",
+                mask_len, signal_len
+            ),
+            WelchError::NoValidSegment => write!(f, "all the segments have missing samples"),
+            WelchError::WindowSize {
+                window_size,
+                segment_size,
+            } => write!(
+                f,
+                "the window size ({}) must match the segment size ({})",
+                window_size, segment_size
+            ),
+            WelchError::RecordLength {
+                record_len,
+                plan_len,
+            } => write!(
+                f,
+                "the record length ({}) must match the length of the records of the plan ({})",
+                record_len, plan_len
+            ),
+            WelchError::UnsupportedCrossSetting(setting) => write!(
+                f,
+                "the cross spectral density does not support the {}",
+                setting
+            ),
+            WelchError::WindowMinSize {
+                min_size,
+                segment_size,
+            } => write!(
+                f,
+                "the segment size ({}) must be at least the window smallest size ({})",
+                segment_size, min_size
+            ),
+            WelchError::UnsupportedStreamingSetting(setting) => write!(
+                f,
+                "the streaming estimator does not support the {}",
+                setting
+            ),
+        }
+    }
+}
+impl Error for WelchError {}
+/// [Periodogram](crate::Periodogram) arithmetic errors
+///
+/// The errors are returned by the element-wise operations (`+`, `-`, `*`, `/`) between periodograms
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeriodogramError {
+    /// the periodograms have a different number of frequency bins
+    Length { left: usize, right: usize },
+    /// one periodogram is one-sided and the other one is two-sided
+    Sides {
+        left_two_sided: bool,
+        right_two_sided: bool,
+    },
+    /// the periodograms frequency grids `f0+i df` differ
+    Frequency {
+        left_f0: f64,
+        left_df: f64,
+        right_f0: f64,
+        right_df: f64,
+    },
+}
+impl Display for PeriodogramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sides = |two_sided: &bool| if *two_sided { "two-sided" } else { "one-sided" };
+        match self {
+            PeriodogramError::Length { left, right } => write!(
+                f,
+                "the periodograms must have the same number of frequency bins ({} and {}), use `Periodogram::interpolate` to resample them on a common grid",
+                left, right
+            ),
+            PeriodogramError::Sides {
+                left_two_sided,
+                right_two_sided,
+            } => write!(
+                f,
+                "the periodograms must be both one-sided or both two-sided ({} and {})",
+                sides(left_two_sided),
+                sides(right_two_sided)
+            ),
+            PeriodogramError::Frequency {
+                left_f0,
+                left_df,
+                right_f0,
+                right_df,
+            } => write!(
+                f,
+                "the periodograms must have the same frequencies (first frequency and resolution: {}Hz and {}Hz vs {}Hz and {}Hz), use `Periodogram::interpolate` to resample them on a common grid",
+                left_f0, left_df, right_f0, right_df
+            ),
+        }
+    }
+}
+impl Error for PeriodogramError {}
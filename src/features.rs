@@ -0,0 +1,108 @@
+use crate::{Periodogram, Signal};
+
+/// Spectral shape features
+///
+/// The periodogram is considered as a distribution of the signal power over the frequencies.
+impl<T: Signal> Periodogram<T> {
+    /// Returns the spectral centroid `c=sum(f P)/sum(P)` in Hz
+    pub fn centroid(&self) -> T {
+        self.moment(T::zero(), 1)
+    }
+    /// Returns the spectral spread `s=sqrt(sum((f-c)^2 P)/sum(P))` in Hz
+    pub fn spread(&self) -> T {
+        self.moment(self.centroid(), 2).sqrt()
+    }
+    /// Returns the spectral skewness `sum((f-c)^3 P)/sum(P)/s^3`
+    pub fn skewness(&self) -> T {
+        let c = self.centroid();
+        self.moment(c, 3) / self.moment(c, 2).powf(T::from_f64(1.5).unwrap())
+    }
+    /// Returns the spectral kurtosis `sum((f-c)^4 P)/sum(P)/s^4`
+    pub fn kurtosis(&self) -> T {
+        let c = self.centroid();
+        self.moment(c, 4) / self.moment(c, 2).powi(2)
+    }
+    /// Returns the spectral centroid, spread, skewness and kurtosis
+    pub fn moments(&self) -> (T, T, T, T) {
+        let c = self.centroid();
+        let var = self.moment(c, 2);
+        (
+            c,
+            var.sqrt(),
+            self.moment(c, 3) / var.powf(T::from_f64(1.5).unwrap()),
+            self.moment(c, 4) / var.powi(2),
+        )
+    }
+    /// Returns the spectral flatness, the ratio of the geometric mean to the arithmetic mean of the periodogram
+    ///
+    /// The flatness is within `[0,1]`, from `0` for a pure tone to `1` for a white noise.
+    /// The zero frequency bin is left out as it holds the signal mean, which is usually removed with [Detrend](crate::Detrend),
+    /// and the power of the other bins is clamped to the smallest positive value of `T`
+    /// such as bins without power bring the flatness toward `0` instead of making it undefined
+    pub fn flatness(&self) -> T {
+        let (n, log_sum, sum) = self
+            .iter()
+            .filter(|(f, _)| !f.is_zero())
+            .map(|(_, p)| p.max(T::min_positive_value()))
+            .fold((0usize, T::zero(), T::zero()), |(n, a, b), p| {
+                (n + 1, a + p.ln(), b + p)
+            });
+        let n = T::from_usize(n).unwrap();
+        (log_sum / n).exp() / (sum / n)
+    }
+    /// Returns the spectral entropy `-sum(p ln(p))/ln(n)` of the periodogram normalized to a probability distribution `p=P/sum(P)`
+    ///
+    /// The entropy is normalized by its maximum `ln(n)`, where `n` is the number of frequency bins,
+    /// such as it is within `[0,1]`, from `0` for a pure tone to `1` for a white noise
+    pub fn entropy(&self) -> T {
+        let sum = self.iter().map(|(_, p)| p).sum::<T>();
+        let h = self
+            .iter()
+            .map(|(_, p)| p / sum)
+            .filter(|&p| p > T::zero())
+            .map(|p| -p * p.ln())
+            .sum::<T>();
+        h / T::from_usize(self.len()).unwrap().ln()
+    }
+    // Moment of order `k` of the frequency about `center` weighted with the periodogram
+    fn moment(&self, center: T, k: i32) -> T {
+        let (sum, weights) = self.iter().fold((T::zero(), T::zero()), |(a, b), (f, p)| {
+            (a + (f - center).powi(k) * p, b + p)
+        });
+        sum / weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Builder, Detrend, Hann, One, Welch};
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn flatness() {
+        let mut rng = StdRng::seed_from_u64(67);
+        let noise: Vec<f64> = (0..100_000)
+            .map(|_| {
+                let e: f64 = StandardNormal.sample(&mut rng);
+                5. + e
+            })
+            .collect();
+        // the mean removed from the segments leaves no power in the zero frequency bin
+        let welch: Welch<f64, One<f64>> = Builder::new(&noise)
+            .segment_size(256)
+            .detrend(Detrend::Constant)
+            .build();
+        let flatness = welch.periodogram().flatness();
+        assert!(flatness > 0.9, "white noise flatness: {flatness}");
+        let tone: Vec<f64> = (0..100_000)
+            .map(|i| 5. + (2. * std::f64::consts::PI * 0.125 * i as f64).sin())
+            .collect();
+        let welch: Welch<f64, Hann<f64>> = Builder::new(&tone)
+            .segment_size(256)
+            .detrend(Detrend::Constant)
+            .build();
+        let flatness = welch.periodogram().flatness();
+        assert!((0. ..1e-2).contains(&flatness), "tone flatness: {flatness}");
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{Sample, Signal, Welch, Window};
+use num_complex::Complex;
+use num_traits::Zero;
+
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
+    /// Returns the periodogram at the given `frequencies` `[Hz]` only
+    ///
+    /// The Fourier transform of each segment is evaluated at each frequency with the Goertzel algorithm,
+    /// with a cost proportional to the segment size for each frequency,
+    /// much cheaper than a full discrete Fourier transform for a few frequencies.
+    /// The frequencies are not restricted to the discrete Fourier transform bins.
+    /// The periodogram is scaled according to [Welch::scaling](Welch#structfield.scaling)
+    /// and the segments are averaged according to [Welch::averaging](Welch#structfield.averaging).
+    pub fn goertzel(&self, frequencies: &[T]) -> Vec<T> {
+        let two_pi = T::from_f64(2. * std::f64::consts::PI).unwrap();
+        let coefficients: Vec<(T, Complex<T>, T)> = frequencies
+            .iter()
+            .map(|&f| {
+                let w = two_pi * f / self.fs;
+                let (sin, cos) = w.sin_cos();
+                (cos + cos, Complex::new(cos, -sin), self.power_scale(f))
+            })
+            .collect();
+        let segments = self.windowed_segments().map(|s| {
+            coefficients
+                .iter()
+                .map(|&(c, e, u)| {
+                    // s[n] = x[n] + 2cos(w)s[n-1] - s[n-2]
+                    let (s1, s2) = s.iter().fold(
+                        (Complex::zero(), Complex::zero()),
+                        |(s1, s2): (Complex<T>, Complex<T>), &x| (x + s1 * c - s2, s1),
+                    );
+                    (s1 - e * s2).norm_sqr() * u
+                })
+                .collect()
+        });
+        self.averaging
+            .average(segments, frequencies.len())
+            .into_iter()
+            .zip(frequencies)
+            .map(|(x, &f)| self.amplitude(self.scaling, f, x))
+            .collect()
+    }
+}
@@ -0,0 +1,292 @@
+use crate::{Periodogram, Sample, Scaling, Signal, Welch, Window, WindowCorrection};
+use hdf5_metno_sys::{
+    h5::{herr_t, hsize_t, H5open},
+    h5a::{H5Aclose, H5Acreate2, H5Awrite},
+    h5d::{H5Dclose, H5Dcreate2, H5Dwrite},
+    h5f::{H5Fclose, H5Fcreate, H5F_ACC_TRUNC},
+    h5g::{H5Gclose, H5Gcreate2},
+    h5i::hid_t,
+    h5p::H5P_DEFAULT,
+    h5s::{H5Sclose, H5Screate, H5Screate_simple, H5S_ALL, H5S_SCALAR},
+    h5t::{H5Tclose, H5Tcopy, H5Tset_size, H5T_C_S1, H5T_NATIVE_DOUBLE, H5T_NATIVE_UINT64},
+};
+use std::{
+    error::Error,
+    ffi::{c_void, CString},
+    fmt::Display,
+    path::Path,
+    ptr,
+};
+
+/// HDF5 writer errors
+///
+/// The error holds the name of the HDF5 object that could not be created or written
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hdf5Error(String);
+impl Display for Hdf5Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HDF5 error: {}", self.0)
+    }
+}
+impl Error for Hdf5Error {}
+
+// Returns the HDF5 identifier or an error if it is invalid
+fn check_id(id: hid_t, context: &str) -> Result<hid_t, Hdf5Error> {
+    if id < 0 {
+        Err(Hdf5Error(format!("failed to create {}", context)))
+    } else {
+        Ok(id)
+    }
+}
+// Returns an error if the HDF5 call status is negative
+fn check_status(status: herr_t, context: &str) -> Result<(), Hdf5Error> {
+    if status < 0 {
+        Err(Hdf5Error(format!("failed to write {}", context)))
+    } else {
+        Ok(())
+    }
+}
+// Converts `name` into a C string
+fn c_name(name: &str) -> Result<CString, Hdf5Error> {
+    CString::new(name).map_err(|_| Hdf5Error(format!("invalid string {:?}", name)))
+}
+
+/// HDF5 file writer of periodograms and spectrograms
+///
+/// Each periodogram is written into its own group, with the `frequency` `[Hz]` and the `value` datasets
+/// and with the sampling frequency `fs`, the frequency resolution `df`, the `two_sided` flag and,
+/// if they are known, the `degrees_of_freedom` as attributes of the group.
+/// The estimator settings, i.e. the `window`, the `n_segment`, `segment_size`, `overlap_size` and `dft_size` dimensions
+/// the `scaling` and the window `correction`, are added to the group attributes by [Hdf5Writer::write_welch] and [Hdf5Writer::write_spectrogram].
+///
+/// The file is closed when the writer is dropped.
+#[derive(Debug)]
+pub struct Hdf5Writer {
+    file: hid_t,
+}
+impl Hdf5Writer {
+    /// Creates the HDF5 file at `path`, truncating it if it exists
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, Hdf5Error> {
+        let name = c_name(&path.as_ref().to_string_lossy())?;
+        let file = unsafe {
+            check_status(H5open(), "HDF5 library initialization")?;
+            H5Fcreate(name.as_ptr(), H5F_ACC_TRUNC, H5P_DEFAULT, H5P_DEFAULT)
+        };
+        Ok(Self {
+            file: check_id(file, &format!("file {:?}", path.as_ref()))?,
+        })
+    }
+    /// Writes the `periodogram` into the group `name`
+    pub fn write_periodogram<T: Signal>(
+        &self,
+        name: &str,
+        periodogram: &Periodogram<T>,
+    ) -> Result<(), Hdf5Error> {
+        let group = Group::create(self.file, name)?;
+        group.write_periodogram(periodogram)
+    }
+    /// Writes the periodogram of the [Welch] estimator into the group `name`, together with the estimator settings
+    pub fn write_welch<T: Signal, W: Window<T>, S: Sample<T>>(
+        &self,
+        name: &str,
+        welch: &Welch<T, W, S>,
+    ) -> Result<(), Hdf5Error> {
+        let group = Group::create(self.file, name)?;
+        group.write_periodogram(&welch.periodogram())?;
+        group.write_settings(welch)
+    }
+    /// Writes the periodogram of each segment of the [Welch] estimator into the group `name`,
+    /// together with the estimator settings
+    ///
+    /// The `value` dataset is an array with one row per segment and one column per frequency bin,
+    /// and the `time` dataset `[s]` is the time at the center of each segment.
+    pub fn write_spectrogram<T: Signal, W: Window<T>, S: Sample<T>>(
+        &self,
+        name: &str,
+        welch: &Welch<T, W, S>,
+    ) -> Result<(), Hdf5Error> {
+        let group = Group::create(self.file, name)?;
+        let segments = welch.segment_periodograms();
+        let n = segments.first().map_or(0, |p| p.len());
+        let fs = welch.fs.to_f64().unwrap();
+        let time: Vec<f64> = welch
+            .segment_starts()
+            .map(|i| i as f64 / fs + 0.5 * welch.segment_size as f64 / fs)
+            .collect();
+        if let Some(p) = segments.first() {
+            group.write_frequency(p)?;
+        }
+        group.write_dataset("time", &[time.len()], &time)?;
+        let values: Vec<f64> = segments
+            .iter()
+            .flat_map(|p| p.iter().map(|(_, x)| x.to_f64().unwrap()))
+            .collect();
+        group.write_dataset("value", &[segments.len(), n], &values)?;
+        group.write_f64("fs", fs)?;
+        group.write_settings(welch)
+    }
+}
+impl Drop for Hdf5Writer {
+    fn drop(&mut self) {
+        unsafe {
+            H5Fclose(self.file);
+        }
+    }
+}
+
+// HDF5 group, closed when dropped
+struct Group(hid_t);
+impl Group {
+    fn create(file: hid_t, name: &str) -> Result<Self, Hdf5Error> {
+        let c = c_name(name)?;
+        let id = unsafe { H5Gcreate2(file, c.as_ptr(), H5P_DEFAULT, H5P_DEFAULT, H5P_DEFAULT) };
+        check_id(id, &format!("group {:?}", name)).map(Self)
+    }
+    fn write_frequency<T: Signal>(&self, periodogram: &Periodogram<T>) -> Result<(), Hdf5Error> {
+        let frequency: Vec<f64> = periodogram
+            .frequency()
+            .into_iter()
+            .map(|f| f.to_f64().unwrap())
+            .collect();
+        self.write_dataset("frequency", &[frequency.len()], &frequency)?;
+        self.write_f64("df", periodogram.df().to_f64().unwrap())?;
+        self.write_u64("two_sided", periodogram.is_two_sided() as u64)
+    }
+    fn write_periodogram<T: Signal>(&self, periodogram: &Periodogram<T>) -> Result<(), Hdf5Error> {
+        self.write_frequency(periodogram)?;
+        let values: Vec<f64> = periodogram
+            .iter()
+            .map(|(_, x)| x.to_f64().unwrap())
+            .collect();
+        self.write_dataset("value", &[values.len()], &values)?;
+        self.write_f64("fs", periodogram.fs().to_f64().unwrap())?;
+        match periodogram.degrees_of_freedom() {
+            Some(dof) => self.write_f64("degrees_of_freedom", dof.to_f64().unwrap()),
+            None => Ok(()),
+        }
+    }
+    fn write_settings<T: Signal, W: Window<T>, S: Sample<T>>(
+        &self,
+        welch: &Welch<T, W, S>,
+    ) -> Result<(), Hdf5Error> {
+        // window type name without its path and its parameters, e.g. `Hann`
+        let window = std::any::type_name::<W>()
+            .split('<')
+            .next()
+            .and_then(|name| name.rsplit("::").next())
+            .unwrap_or_default();
+        self.write_str("window", window)?;
+        self.write_u64("n_segment", welch.n_segment as u64)?;
+        self.write_u64("segment_size", welch.segment_size as u64)?;
+        self.write_u64(
+            "overlap_size",
+            (welch.segment_size - welch.overlap_idx) as u64,
+        )?;
+        self.write_u64("dft_size", welch.dft_size as u64)?;
+        self.write_str(
+            "scaling",
+            match welch.scaling {
+                Scaling::Density => "density",
+                Scaling::Spectrum => "spectrum",
+                Scaling::RmsAmplitude => "rms_amplitude",
+                Scaling::PeakAmplitude => "peak_amplitude",
+            },
+        )?;
+        self.write_str(
+            "correction",
+            match welch.correction {
+                WindowCorrection::Auto => "auto",
+                WindowCorrection::Amplitude => "amplitude",
+                WindowCorrection::Energy => "energy",
+            },
+        )
+    }
+    // Writes the `data` array of shape `shape` into the dataset `name`
+    fn write_dataset(&self, name: &str, shape: &[usize], data: &[f64]) -> Result<(), Hdf5Error> {
+        let c = c_name(name)?;
+        let dims: Vec<hsize_t> = shape.iter().map(|&n| n as hsize_t).collect();
+        let context = format!("dataset {:?}", name);
+        unsafe {
+            let space = check_id(
+                H5Screate_simple(dims.len() as _, dims.as_ptr(), ptr::null()),
+                &context,
+            )?;
+            let dataset = H5Dcreate2(
+                self.0,
+                c.as_ptr(),
+                *H5T_NATIVE_DOUBLE,
+                space,
+                H5P_DEFAULT,
+                H5P_DEFAULT,
+                H5P_DEFAULT,
+            );
+            H5Sclose(space);
+            let dataset = check_id(dataset, &context)?;
+            let status = H5Dwrite(
+                dataset,
+                *H5T_NATIVE_DOUBLE,
+                H5S_ALL,
+                H5S_ALL,
+                H5P_DEFAULT,
+                data.as_ptr() as *const c_void,
+            );
+            H5Dclose(dataset);
+            check_status(status, &context)
+        }
+    }
+    // Writes the scalar attribute `name` of type `dtype` from `value`
+    fn write_attribute(
+        &self,
+        name: &str,
+        dtype: hid_t,
+        value: *const c_void,
+    ) -> Result<(), Hdf5Error> {
+        let c = c_name(name)?;
+        let context = format!("attribute {:?}", name);
+        unsafe {
+            let space = check_id(H5Screate(H5S_SCALAR), &context)?;
+            let attribute = H5Acreate2(self.0, c.as_ptr(), dtype, space, H5P_DEFAULT, H5P_DEFAULT);
+            H5Sclose(space);
+            let attribute = check_id(attribute, &context)?;
+            let status = H5Awrite(attribute, dtype, value);
+            H5Aclose(attribute);
+            check_status(status, &context)
+        }
+    }
+    fn write_f64(&self, name: &str, value: f64) -> Result<(), Hdf5Error> {
+        self.write_attribute(
+            name,
+            *H5T_NATIVE_DOUBLE,
+            &value as *const f64 as *const c_void,
+        )
+    }
+    fn write_u64(&self, name: &str, value: u64) -> Result<(), Hdf5Error> {
+        self.write_attribute(
+            name,
+            *H5T_NATIVE_UINT64,
+            &value as *const u64 as *const c_void,
+        )
+    }
+    // Writes the null-terminated string attribute `name`
+    fn write_str(&self, name: &str, value: &str) -> Result<(), Hdf5Error> {
+        let value = c_name(value)?;
+        let context = format!("attribute {:?}", name);
+        unsafe {
+            let dtype = check_id(H5Tcopy(*H5T_C_S1), &context)?;
+            let result = check_status(
+                H5Tset_size(dtype, value.as_bytes_with_nul().len()),
+                &context,
+            )
+            .and_then(|_| self.write_attribute(name, dtype, value.as_ptr() as *const c_void));
+            H5Tclose(dtype);
+            result
+        }
+    }
+}
+impl Drop for Group {
+    fn drop(&mut self) {
+        unsafe {
+            H5Gclose(self.0);
+        }
+    }
+}
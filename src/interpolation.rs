@@ -0,0 +1,70 @@
+use crate::{Periodogram, Signal};
+
+/// Interpolation method of the periodogram between the frequency bins
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Interpolation {
+    /// linear interpolation of the values with respect to the frequency
+    #[default]
+    Linear,
+    /// linear interpolation of the logarithm of the values with respect to the logarithm of the frequency,
+    /// exact for power laws
+    Log,
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the periodogram values at the `frequency` `[Hz]` grid
+    ///
+    /// The values are interpolated between the 2 nearest frequency bins according to `method`.
+    /// With the [Log](Interpolation::Log) interpolation, the values are clamped to the smallest positive value,
+    /// and the logarithm of the values is interpolated linearly with respect to the frequency between the zero frequency
+    /// bin and its neighbors.
+    /// The frequencies outside the range of the periodogram frequencies are set to `NaN`.
+    ///
+    /// Note that the interpolated values are not rescaled: a spectral density remains a density on the new grid
+    /// whereas the power of a power spectrum is not conserved if the grid spacing differs from [Periodogram::df].
+    pub fn interpolate(&self, frequency: &[T], method: Interpolation) -> Vec<T> {
+        let n = self.len();
+        let df = self.df();
+        let f0 = self.f0();
+        let f_last = f0 + T::from_usize(n - 1).unwrap() * df;
+        let log = |x: T| x.max(T::min_positive_value()).ln();
+        frequency
+            .iter()
+            .map(|&f| {
+                if f.is_nan() || f < f0 || f > f_last {
+                    return T::nan();
+                }
+                if n == 1 {
+                    return self[0];
+                }
+                let x = (f - f0) / df;
+                let i = x.floor().to_usize().unwrap().min(n - 2);
+                let (fa, fb) = (
+                    f0 + T::from_usize(i).unwrap() * df,
+                    f0 + T::from_usize(i + 1).unwrap() * df,
+                );
+                let (a, b) = (self[i], self[i + 1]);
+                match method {
+                    Interpolation::Linear => {
+                        let w = x - T::from_usize(i).unwrap();
+                        a + (b - a) * w
+                    }
+                    Interpolation::Log => {
+                        let w = if fa * fb > T::zero() {
+                            (f.abs() / fa.abs()).ln() / (fb.abs() / fa.abs()).ln()
+                        } else {
+                            x - T::from_usize(i).unwrap()
+                        };
+                        (log(a) + (log(b) - log(a)) * w).exp()
+                    }
+                }
+            })
+            .collect()
+    }
+    /// Returns the periodogram value at the frequency `f` `[Hz]` interpolated according to `method`
+    ///
+    /// See [Periodogram::interpolate]; the value is `NaN` outside the range of the periodogram frequencies.
+    /// The value of the nearest frequency bin is given by [Periodogram::value_at].
+    pub fn interpolate_at(&self, f: T, method: Interpolation) -> T {
+        self.interpolate(&[f], method)[0]
+    }
+}
@@ -7,20 +7,102 @@
 //! The periodogram is then given by the average of the squared magnitude
 //! of each Fourier transform.
 //! Only the halve of the power spectrum that corresponds to positive frequencies is returned,
+//! from the zero frequency up to and including the Nyquist frequency,
 //! both halves beeing symmetric with respect to the zero frequency.
-//!
+//! The positive frequencies, except the zero and Nyquist frequencies, are doubled to account for
+//! the negative frequencies, such as the sum of the power spectrum is the signal variance.
+//! The full two-sided spectrum is returned instead with [Builder::two_sided].
 //!
 //! From the periodogram, one can derive either the **spectral density** or the **power spectrum**.
 //! Both differs with respect to the scaling of the periodogram.
 //! For the **spectral density**, the periodogram is divided by the product of the sampling frequency with the sum of the squared window samples.
 //! For the **power spectrum**, the periodogram is divided by the square of the sum of the window samples.
+//! The **amplitude spectrum**, either the root-mean-square or the peak amplitude of the tones in the signal unit,
+//! is the square root of the power spectrum ([Scaling::RmsAmplitude] and [Scaling::PeakAmplitude]).
+//!
+//! ## Estimators
 //!
-//! The Welch algorithm is implemented in the [Welch] structure.
+//! The Welch algorithm is implemented in the [Welch] structure, set up with a [Builder].
+//! [Welch::periodogram] returns either the spectral density or the power spectrum according to the [Scaling] set with [Builder::scaling],
+//! and [Welch::scaled_periodogram] returns both from the same estimator.
 //! For convenience, 2 new types (that encapsulates [Welch]) are provided, [SpectralDensity] and [PowerSpectrum], to compute  the **spectral density** and the **power spectrum**, respectively.
 //! [SpectralDensity] uses a [Hann] window whereas [PowerSpectrum] does not use any window.
+//! The estimators can also be parameterized like `scipy.signal.welch` with [Scipy].
+//! Invalid settings make [Build::build] panic, whereas [Builder::try_build] returns a [WelchError].
+//!
+//! Other estimators are:
+//!  - [CrossSpectralDensity], the cross spectral density of 2 signals,
+//!  - [TransferFunction], the transfer function and the coherence of a system,
+//!  - [Multitaper], the average of the eigenspectra of [Dpss] tapers,
+//!  - [BlackmanTukey], the Fourier transform of the windowed signal autocorrelation,
+//!  - [LombScargle], the spectral density of unevenly sampled signals,
+//!  - [ArSpectrum], a parametric autoregressive spectral density,
+//!  - [Waterfall] and [Coherogram], the evolution of the periodogram and of the coherence over the blocks of a long record,
+//!  - [Welch2d], the [Periodogram2d] of images and other 2D arrays, summarized by its [RadialPeriodogram],
+//!  - [cross_correlation] and [autocorrelation], computed with the discrete Fourier transform.
+//!
+//! Multiple channels of the same length share the settings of an estimator with [Welch::with_signal],
+//! and a large collection of equally-sized records is processed with a [WelchPlan] built once.
+//! Signals split into chunks are segmented across the chunks boundaries with [Builder::from_chunks],
+//! and signals acquired in real time, or given by an iterator, are pushed incrementally into a [WelchAccumulator],
+//! built from a [Builder] given the signal length with [Builder::from_len] and [Builder::accumulate].
+//!
+//! ## Signals
 //!
-//! Custom windows can be used with [Welch] if they implement the [Window] trait.
 //! The signal is either a [single](f32) or [double](f64) floating point array.
+//! Complex signals (`Complex<f32>` or `Complex<f64>`), like I/Q baseband data, are also supported (see [Sample]),
+//! their periodogram is two-sided with frequencies within `[-fs/2,fs/2)`.
+//! The signal is prepared before the segments periodogram with the [Builder]:
+//!  - [Builder::detrend] removes an offset or a drift from each segment,
+//!  - [Builder::nan_policy] rejects, skips or interpolates the NaN samples according to the [NanPolicy],
+//!  - [Builder::mask] and [Builder::gaps] skip the segments within the gaps of the signal,
+//!  - [Builder::decimation] low-pass filters and decimates oversampled signals,
+//!  - [Builder::calibration] gives the periodograms in physical units with the [Calibration] of a sensor.
+//!
+//! The segments periodogram are averaged with the arithmetic mean, or with a more robust [Averaging] method set with [Builder::averaging],
+//! and the equivalent degrees of freedom of the average are given by [Welch::degrees_of_freedom].
+//!
+//! ## Periodograms
+//!
+//! A [Periodogram] holds the values with its sampling frequency and frequency grid.
+//! It is analyzed with:
+//!  - [Periodogram::peaks], the tones frequency and power interpolated between the bins,
+//!  - [Periodogram::noise_floor], with one of the [NoiseFloor] methods,
+//!  - [Periodogram::power_law_fit], a [PowerLaw] model of flicker noise over a white noise floor,
+//!  - [Periodogram::smoothed], [Periodogram::interpolate] and [Periodogram::octave_bands],
+//!  - [Periodogram::cumulative], [Periodogram::cumulative_rms] and [Periodogram::autocovariance],
+//!  - [Periodogram::moments], [Periodogram::flatness] and [Periodogram::entropy],
+//!  - [Periodogram::synthesize], noise signals [synthesized](synthesize) according to the [Synthesis] method,
+//!  - [Periodogram::to_csv], the export to spreadsheets or plotting tools.
+//!
+//! Periodograms with the same frequencies are added, subtracted, multiplied or divided bin by bin,
+//! the operations returning a [PeriodogramError] if the frequencies differ.
+//! The [Welch] estimator also checks the Parseval theorem with [Welch::total_power] and [Welch::variance],
+//! measures the harmonic [Distortion] of a tone with [Welch::distortion],
+//! zooms on a narrow frequency band with [Welch::zoom] and tracks a few frequencies with [Welch::goertzel].
+//!
+//! ## Windows
+//!
+//! [Hann] is the default window; other windows, like [Hamming] or [Blackman], are set with the [Welch] type parameter,
+//! and windows with parameters, like [Kaiser], are given to the estimator with [Builder::window].
+//! Custom windows can be used with [Welch] if they implement the [Window] trait.
+//! Windows are periodic by default, symmetric windows are selected with [Builder::window_symmetry].
+//! The window attenuation is compensated with either the amplitude-correction or the energy-correction factor
+//! according to the [WindowCorrection] set with [Builder::window_correction].
+//!
+//! ## Features
+//!
+//!  - `ndarray`: signals and periodograms as `ndarray` arrays,
+//!  - `arrow`: signals as Arrow arrays and periodograms as Arrow record batches,
+//!  - `wasm`: JavaScript bindings of [SpectralDensity] and [WelchAccumulator] with `wasm-bindgen`,
+//!  - `wav`: signals read from WAV files with `WavSignal`,
+//!  - `hdf5`: periodograms and spectrograms written to HDF5 files with `Hdf5Writer` (requires the HDF5 library),
+//!  - `cli`: the `welch` binary, estimating the periodogram of a CSV, raw binary or WAV file,
+//!  - `mmap`: memory-mapped recordings larger than the memory with `MmapSignal`,
+//!  - `cuda`: the CUDA GPU [Backend] of [Welch], selected with [Builder::backend],
+//!  - `uom`: signals of `uom` quantities with `QuantityWelch`, propagating the signal unit to the periodograms,
+//!  - `simd`: vectorized segments windowing and periodograms accumulation with the `wide` crate,
+//!  - `serde`: serialization of the periodograms.
 //!
 //! ## Examples
 //! ### Power spectrum
@@ -51,8 +133,12 @@
 //!        now.elapsed().as_millis()
 //!    );
 //!    {
-//!        let variance = 2. * ps.iter().sum::<f64>();
+//!        let variance = ps.iter().map(|(_, p)| p).sum::<f64>();
 //!        println!("Signal variance from power spectrum: {:.3}", variance);
+//!        println!(
+//!            "Signal variance from the spectral density: {:.3}",
+//!            welch.variance()
+//!        );
 //!    }
 //!}
 //!```
@@ -61,7 +147,7 @@
 //!use rand::prelude::*;
 //!use rand_distr::StandardNormal;
 //!use std::time::Instant;
-//!use welch_sde::{SpectralDensity, Build};
+//!use welch_sde::{Build, NoiseFloor, SpectralDensity};
 //!
 //!fn main() {
 //!    let n = 1e5 as usize;
@@ -87,14 +173,11 @@
 //!        "Spectral density estimated in {}ms",
 //!        now.elapsed().as_millis()
 //!    );
-//!    let noise_floor = sd.iter().cloned().sum::<f64>() / sd.len() as f64;
-//!    println!("Noise floor: {:.3}", noise_floor);
+//!    let (noise_floor, error) = sd.noise_floor(NoiseFloor::Median, Some(1e-2));
+//!    println!("Noise floor: {:.3}+/-{:.3}", noise_floor, error);
 //!
 //!    let _: complot::LinLog = (
-//!        sd.frequency()
-//!            .into_iter()
-//!            .zip(&(*sd))
-//!            .map(|(x, &y)| (x, vec![y])),
+//!        sd.iter().map(|(x, y)| (x, vec![y])),
 //!        complot::complot!(
 //!            "spectral_density.png",
 //!            xlabel = "Frequency [Hz]",
@@ -105,22 +188,122 @@
 //!}
 //!```
 
+mod ar_spectrum;
+mod arithmetic;
+#[cfg(feature = "ndarray")]
+mod array;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod autocovariance;
+mod backend;
+mod blackman_tukey;
 mod builder;
+mod calibration;
+mod chunks;
+mod coherogram;
+mod correlation;
+mod cross_spectral_density;
+mod csv;
+#[cfg(feature = "cuda")]
+mod cuda;
+mod cumulative;
+mod decimation;
+mod detrend;
+mod dft;
+mod distortion;
+mod error;
+mod features;
+mod goertzel;
+#[cfg(feature = "hdf5")]
+mod hdf5;
+mod interpolation;
+mod lomb_scargle;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod multitaper;
+mod nan;
+mod noise_floor;
+mod octave;
+mod parseval;
+mod peaks;
 mod periodogram;
+mod periodogram2d;
+mod power_law;
 mod power_spectrum;
+mod radial;
+mod scipy;
+mod simd;
+mod smoothing;
 mod spectral_density;
+mod synthesis;
+mod transfer_function;
+#[cfg(feature = "uom")]
+mod uom;
+mod waterfall;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wav")]
+mod wav;
 mod welch;
+mod welch_accumulator;
+mod welch_plan;
+mod welch2d;
 mod window;
+mod zoom;
+pub use ar_spectrum::{ArMethod, ArSpectrum};
+pub use backend::Backend;
+pub use blackman_tukey::BlackmanTukey;
 pub use builder::Builder;
+pub use calibration::Calibration;
+pub use coherogram::Coherogram;
+pub use correlation::{autocorrelation, cross_correlation, CorrelationBias};
+pub use cross_spectral_density::CrossSpectralDensity;
+pub use detrend::Detrend;
+pub use distortion::Distortion;
+pub use error::{PeriodogramError, WelchError};
+#[cfg(feature = "hdf5")]
+pub use hdf5::{Hdf5Error, Hdf5Writer};
+pub use interpolation::Interpolation;
+pub use lomb_scargle::LombScargle;
+#[cfg(feature = "mmap")]
+pub use mmap::MmapSignal;
+pub use multitaper::Multitaper;
+pub use nan::NanPolicy;
+pub use noise_floor::NoiseFloor;
+use num_complex::Complex;
 use num_traits::Float;
+pub use octave::{OctaveBand, OctaveBase};
+pub use peaks::Peak;
 pub use periodogram::{Periodogram, PowerSpectrumPeriodogram, SpectralDensityPeriodogram};
+pub use periodogram2d::Periodogram2d;
+pub use power_law::PowerLaw;
 pub use power_spectrum::PowerSpectrum;
+pub use radial::RadialPeriodogram;
 use rustfft::FftNum;
+pub use scipy::Scipy;
 pub use spectral_density::SpectralDensity;
-pub use welch::Welch;
-pub use window::{Hann, One, Window};
+pub use synthesis::{synthesize, Synthesis};
+pub use transfer_function::TransferFunction;
+#[cfg(feature = "uom")]
+pub use uom::{QuantityBuilder, QuantityPeriodogram, QuantityWelch, SpectralDimension};
+pub use waterfall::Waterfall;
+#[cfg(feature = "wasm")]
+pub use wasm::{WasmSpectralDensity, WasmWelchAccumulator};
+#[cfg(feature = "wav")]
+pub use wav::WavSignal;
+pub use welch::{Averaging, Scaling, Welch, WindowCorrection};
+pub use welch_accumulator::WelchAccumulator;
+pub use welch_plan::WelchPlan;
+pub use welch2d::Welch2d;
+pub use window::{
+    Bartlett, Blackman, BlackmanHarris, CosineSum, Custom, DolphChebyshev, Dpss, Exponential,
+    FnWindow, Hamming, Hann, Kaiser, One, Parabolic, Symmetry, Window,
+};
 
 /// The trait the signal type `T` must implement
+///
+/// It is implemented for `f32` and `f64`, and it can be implemented for any other floating point type
+/// supported by the Fourier transforms of `rustfft`.
 pub trait Signal:
     Float + FftNum + std::iter::Sum + std::ops::SubAssign + std::ops::AddAssign
 {
@@ -128,8 +311,49 @@ pub trait Signal:
 impl Signal for f64 {}
 impl Signal for f32 {}
 
+/// The trait the signal samples must implement
+///
+/// It is implemented for real samples of type `T` and for complex samples of type `Complex<T>`,
+/// the periodogram of a complex signal is two-sided
+pub trait Sample<T: Signal>: Copy + std::fmt::Debug + Send + Sync {
+    /// `true` for complex samples
+    const COMPLEX: bool;
+    /// Converts the sample into a complex number
+    fn to_complex(self) -> Complex<T>;
+    /// Converts a complex number into a sample, discarding the imaginary part for real samples
+    fn from_complex(c: Complex<T>) -> Self;
+    /// Returns the samples as a slice of real numbers, or `None` for complex samples
+    fn as_real(samples: &[Self]) -> Option<&[T]>;
+}
+impl<T: Signal> Sample<T> for T {
+    const COMPLEX: bool = false;
+    fn to_complex(self) -> Complex<T> {
+        Complex::new(self, T::zero())
+    }
+    fn from_complex(c: Complex<T>) -> Self {
+        c.re
+    }
+    fn as_real(samples: &[Self]) -> Option<&[T]> {
+        Some(samples)
+    }
+}
+impl<T: Signal> Sample<T> for Complex<T> {
+    const COMPLEX: bool = true;
+    fn to_complex(self) -> Complex<T> {
+        self
+    }
+    fn from_complex(c: Complex<T>) -> Self {
+        c
+    }
+    fn as_real(_samples: &[Self]) -> Option<&[T]> {
+        None
+    }
+}
+
 /// [Builder] trait
 pub trait Build<E> {
     /// Returns a struct `E` initialized according to the [Builder] settings
+    ///
+    /// Panics if the settings are invalid, see [Builder::try_build] for a fallible alternative
     fn build(&self) -> E;
 }
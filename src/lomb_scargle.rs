@@ -0,0 +1,169 @@
+use crate::{Periodogram, Signal};
+
+/// Lomb-Scargle periodogram
+///
+/// Estimates the spectral density of a signal `x` sampled at arbitrary times `t`, by least-squares fitting
+/// of sinusoids at each frequency.
+/// The periodogram is computed on `n_f` evenly spaced frequencies from 0 to `f_max`;
+/// by default, `f_max` is the average Nyquist frequency `n/(2T)`, where `n` is the number of samples and `T`
+/// the time span of the signal, and `n_f=n/2+1`.
+/// The periodogram is scaled as a spectral density with the average sampling frequency `n/T`.
+#[derive(Debug, Clone)]
+pub struct LombScargle<'a, T: Signal> {
+    t: &'a [T],
+    x: &'a [T],
+    f_max: T,
+    n_frequency: usize,
+}
+impl<'a, T: Signal> LombScargle<'a, T> {
+    /// Creates a new Lomb-Scargle estimator for the signal `x` sampled at the times `t`
+    pub fn new(t: &'a [T], x: &'a [T]) -> Self {
+        assert_eq!(t.len(), x.len(), "t and x must have the same length");
+        let n = x.len();
+        Self {
+            t,
+            x,
+            f_max: T::from_usize(n).unwrap() / (T::from_f64(2.).unwrap() * time_span(t)),
+            n_frequency: (n / 2 + 1).max(2),
+        }
+    }
+    /// Sets the maximum frequency `[Hz]`
+    pub fn max_frequency(self, f_max: T) -> Self {
+        Self { f_max, ..self }
+    }
+    /// Sets the number of frequencies, at least 2, from 0 to the maximum frequency
+    pub fn n_frequency(self, n_frequency: usize) -> Self {
+        assert!(
+            n_frequency >= 2,
+            "the number of frequencies ({n_frequency}) must be at least 2"
+        );
+        Self {
+            n_frequency,
+            ..self
+        }
+    }
+    /// Returns the Lomb-Scargle periodogram
+    pub fn periodogram(&self) -> Periodogram<T> {
+        let n = T::from_usize(self.x.len()).unwrap();
+        let mean = self.x.iter().cloned().sum::<T>() / n;
+        let two = T::from_f64(2.).unwrap();
+        let two_pi = T::from_f64(2. * std::f64::consts::PI).unwrap();
+        let df = self.f_max / T::from_usize(self.n_frequency - 1).unwrap();
+        let u = time_span(self.t) / (two * n);
+        let values = (0..self.n_frequency)
+            .map(|i| {
+                let w = two_pi * T::from_usize(i).unwrap() * df;
+                let (s2, c2) = self
+                    .t
+                    .iter()
+                    .map(|&t| (two * w * t).sin_cos())
+                    .fold((T::zero(), T::zero()), |(a, b), (s, c)| (a + s, b + c));
+                let tau = s2.atan2(c2) / (two * w);
+                let (xc, xs, cc, ss) = self
+                    .t
+                    .iter()
+                    .zip(self.x)
+                    .map(|(&t, &x)| {
+                        let (s, c) = if w.is_zero() {
+                            (T::zero(), T::one())
+                        } else {
+                            (w * (t - tau)).sin_cos()
+                        };
+                        (x - mean, s, c)
+                    })
+                    .fold(
+                        (T::zero(), T::zero(), T::zero(), T::zero()),
+                        |(xc, xs, cc, ss), (x, s, c)| {
+                            (xc + x * c, xs + x * s, cc + c * c, ss + s * s)
+                        },
+                    );
+                let p_c = if cc.is_zero() {
+                    T::zero()
+                } else {
+                    xc * xc / cc
+                };
+                let p_s = if ss.is_zero() {
+                    T::zero()
+                } else {
+                    xs * xs / ss
+                };
+                // one-sided: the non-zero frequencies are doubled
+                if i > 0 {
+                    two * (p_c + p_s) * u
+                } else {
+                    (p_c + p_s) * u
+                }
+            })
+            .collect();
+        Periodogram::from_values(two * self.f_max, 2 * (self.n_frequency - 1), values)
+    }
+}
+// Returns the time span of the samples
+fn time_span<T: Signal>(t: &[T]) -> T {
+    let (t_min, t_max) = t
+        .iter()
+        .fold((T::infinity(), T::neg_infinity()), |(a, b), &t| {
+            (a.min(t), b.max(t))
+        });
+    t_max - t_min
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    // `n` sorted random sampling times over `[0,t_max]`
+    fn times(rng: &mut StdRng, n: usize, t_max: f64) -> Vec<f64> {
+        let mut t: Vec<f64> = (0..n).map(|_| rng.gen::<f64>() * t_max).collect();
+        t.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        t
+    }
+
+    #[test]
+    fn white_noise_level() {
+        let mut rng = StdRng::seed_from_u64(30);
+        let (n, sigma) = (2000, 2.);
+        let t = times(&mut rng, n, 100.);
+        let x: Vec<f64> = (0..n)
+            .map(|_| {
+                let e: f64 = StandardNormal.sample(&mut rng);
+                sigma * e
+            })
+            .collect();
+        let periodogram = LombScargle::new(&t, &x).periodogram();
+        let p = &periodogram[1..];
+        let mean = p.iter().sum::<f64>() / p.len() as f64;
+        // the spectral density with the average sampling frequency n/T
+        let var = x.iter().map(|x| x * x).sum::<f64>() / n as f64;
+        let level = 2. * var * time_span(&t) / n as f64;
+        assert!((mean / level - 1.).abs() < 5e-2, "{mean} vs {level}");
+    }
+
+    #[test]
+    fn tone() {
+        let mut rng = StdRng::seed_from_u64(30);
+        let (n, f0, a) = (2000, 2.5, 1.5);
+        let t = times(&mut rng, n, 100.);
+        let x: Vec<f64> = t
+            .iter()
+            .map(|t| a * (2. * std::f64::consts::PI * f0 * t).sin())
+            .collect();
+        // frequencies 8 times finer than the resolution 1/T
+        let periodogram = LombScargle::new(&t, &x)
+            .max_frequency(5.)
+            .n_frequency(4001)
+            .periodogram();
+        let (f, p) = periodogram.max().unwrap();
+        let df = periodogram.df();
+        assert!((f - f0).abs() <= df, "{f} vs {f0}");
+        // the peak of the spectral density is the tone power times the time span
+        let power = p / time_span(&t);
+        assert!(
+            (power / (0.5 * a * a) - 1.).abs() < 2e-2,
+            "{power} vs {}",
+            0.5 * a * a
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{Sample, Signal};
+use memmap2::{Mmap, MmapOptions};
+use std::{fs::File, io, marker::PhantomData, mem, ops::Deref, path::Path, slice};
+
+/// Signal memory-mapped from a raw binary file
+///
+/// The file holds the native-endian samples of type `S`, either real (`S=T`) or complex ([Sample]),
+/// after an optional header of `offset` bytes.
+/// The signal derefs to a slice `&[S]` that is given to the estimator [Builder](crate::Builder) as any other signal.
+///
+/// The [Welch](crate::Welch) estimator streams through the signal one segment at a time,
+/// such that only the pages of the current segments are loaded in memory by the operating system.
+/// Note that [decimation](crate::Builder::decimation), [BlackmanTukey](crate::BlackmanTukey) and
+/// [ArSpectrum](crate::ArSpectrum) process the whole signal at once and copy or transform it.
+#[derive(Debug)]
+pub struct MmapSignal<T: Signal, S: Sample<T> = T> {
+    mmap: Mmap,
+    len: usize,
+    sample: PhantomData<(T, S)>,
+}
+impl<T: Signal, S: Sample<T>> MmapSignal<T, S> {
+    /// Memory maps the file at `path`
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, see [Mmap::map],
+    /// and any bit pattern must be a valid sample, as for `f32`, `f64` and their complex numbers
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::open_with_offset(path, 0)
+    }
+    /// Memory maps the file at `path`, skipping the first `offset` bytes of the file
+    ///
+    /// An error is returned if the size of the file after the offset is not a multiple of the size of a sample,
+    /// or if the samples are not aligned in memory because of the offset.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped, see [Mmap::map],
+    /// and any bit pattern must be a valid sample, as for `f32`, `f64` and their complex numbers
+    pub unsafe fn open_with_offset<P: AsRef<Path>>(path: P, offset: u64) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = MmapOptions::new().offset(offset).map(&file)?;
+        let size = mem::size_of::<S>();
+        if !mmap.len().is_multiple_of(size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "the file size ({} bytes) is not a multiple of the sample size ({} bytes)",
+                    mmap.len(),
+                    size
+                ),
+            ));
+        }
+        if !(mmap.as_ptr() as usize).is_multiple_of(mem::align_of::<S>()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "the offset ({} bytes) does not align the samples in memory",
+                    offset
+                ),
+            ));
+        }
+        Ok(Self {
+            len: mmap.len() / size,
+            mmap,
+            sample: PhantomData,
+        })
+    }
+}
+impl<T: Signal, S: Sample<T>> Deref for MmapSignal<T, S> {
+    type Target = [S];
+
+    fn deref(&self) -> &Self::Target {
+        // any bit pattern is a valid sample (see the safety section of `open`) and the alignment is checked on creation
+        unsafe { slice::from_raw_parts(self.mmap.as_ptr() as *const S, self.len) }
+    }
+}
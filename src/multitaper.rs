@@ -0,0 +1,294 @@
+use crate::{Build, Builder, Dpss, Periodogram, Sample, Signal, Symmetry, Welch, Window};
+use std::{fmt::Display, ops::Deref};
+
+type WelchDpss<'a, T, S> = Welch<'a, T, Dpss<T>, S>;
+
+/// Multitaper spectral density
+///
+/// Computes a `signal` spectral density with the Thomson multitaper method:
+/// each segment is multiplied by `K` orthogonal [Dpss] tapers of orders `0` to `K-1` and the
+/// periodograms of all the tapered segments are averaged.
+/// The time-bandwidth product `NW` of the tapers is given by the [Dpss] window of the [Builder]
+/// (`NW=4` by default) and `K=2NW-1` unless set with [Multitaper::n_taper].
+///
+/// By default, the [Builder] splits the signal in a single segment (`k=1`), unless the segment size
+/// is larger than the maximum size of the discrete Fourier transform, the multitaper estimates of the `k` segments
+/// being then averaged.
+/// The periodogram is scaled according to the [Scaling](crate::Scaling) and the [WindowCorrection](crate::WindowCorrection)
+/// of the [Builder], each eigenspectrum being corrected for its own taper, and calibrated with the [Calibration](crate::Calibration)
+/// of the [Builder].
+///
+/// The eigenspectra of the tapers are either averaged with equal weights or,
+/// with [Multitaper::adaptive], with the Thomson adaptive weights.
+#[derive(Debug, Clone)]
+pub struct Multitaper<'a, T: Signal, S: Sample<T> = T> {
+    welch: WelchDpss<'a, T, S>,
+    symmetry: Symmetry,
+    tapers: Vec<Dpss<T>>,
+    adaptive: bool,
+}
+impl<'a, T: Signal, S: Sample<T>> Multitaper<'a, T, S> {
+    /// Returns [Welch] [Builder] given the `signal` sampled at `fs`Hz
+    pub fn builder(signal: &[S], fs: T) -> Builder<'_, T, Dpss<T>, S> {
+        Builder::new(signal).sampling_frequency(fs).n_segment(1)
+    }
+    /// Sets the number of tapers `K`
+    pub fn n_taper(self, k: usize) -> Self {
+        let tapers = tapers(&self.welch.window, k, self.symmetry);
+        Self { tapers, ..self }
+    }
+    /// Sets the Thomson adaptive weighting of the eigenspectra (disabled by default)
+    ///
+    /// The higher order tapers leak the power of the strong spectral components to the neighboring frequencies,
+    /// biasing the spectral density where the signal is weak.
+    /// At each frequency, the eigenspectrum `S_k` of the taper with the concentration `lambda_k` ([Dpss::concentration])
+    /// is weighted with `d_k=sqrt(lambda_k)S/(lambda_k S+(1-lambda_k)s2)`, `S` being the spectral density
+    /// and `s2` the broadband level, the mean of the spectral density over the frequency bins.
+    /// The spectral density `S=sum(d_k^2 S_k)/sum(d_k^2)` is solved iteratively, starting from the average of the
+    /// eigenspectra of the 2 first tapers.
+    /// The degrees of freedom of the estimate, `2sum(d_k^2)^2/sum(d_k^4)`, vary with the frequency
+    /// and the periodogram degrees of freedom are not set.
+    pub fn adaptive(self, adaptive: bool) -> Self {
+        Self { adaptive, ..self }
+    }
+    /// Returns the tapers
+    pub fn tapers(&self) -> &[Dpss<T>] {
+        self.tapers.as_slice()
+    }
+    // Returns the eigenspectra, the periodograms of the segments for each taper scaled according to the [Builder] scaling
+    pub(crate) fn eigenspectra(&self) -> Vec<Vec<T>> {
+        let welch = &self.welch;
+        let n = welch.n_bins();
+        let k = T::from_usize(welch.n_segment).unwrap();
+        self.tapers
+            .iter()
+            .map(|taper| {
+                let u = welch.scale_with(welch.scaling, taper) / k;
+                welch
+                    .segments_power_with(taper.weights())
+                    .fold(vec![T::zero(); n], |mut a, p| {
+                        a.iter_mut().zip(p).for_each(|(a, p)| *a += p);
+                        a
+                    })
+                    .into_iter()
+                    .map(|x| x * u)
+                    .collect()
+            })
+            .collect()
+    }
+    /// Returns the multitaper periodogram
+    ///
+    /// The periodogram is scaled according to [Welch::scaling](Welch#structfield.scaling).
+    /// With equal weights, the degrees of freedom of the estimate are `2Kk/c`, where `c=1+2sum_j(1-j/k)rho_j` is the correlation
+    /// factor of the overlapping segments, `c=1` for a single segment.
+    /// The correlation of the estimates of segments `j` apart, `rho_j=sum_ab r_ab^2/K`, sums the correlations `r_ab` of the tapers `a`
+    /// with the tapers `b` shifted by `j` segments, as the higher order tapers weight the ends of the overlapping segments.
+    pub fn periodogram(&self) -> Periodogram<T> {
+        let welch = &self.welch;
+        if self.adaptive {
+            let periodogram = welch.to_periodogram(welch.calibrated(self.adaptive_weighting()));
+            return welch.amplitude_spectrum(welch.scaling, periodogram);
+        }
+        let n = welch.n_bins();
+        let u = T::from_usize(self.tapers.len()).unwrap().recip();
+        let values = self
+            .eigenspectra()
+            .into_iter()
+            .fold(vec![T::zero(); n], |mut a, p| {
+                a.iter_mut().zip(p).for_each(|(a, p)| *a += p);
+                a
+            })
+            .into_iter()
+            .map(|x| x * u)
+            .collect();
+        let k = welch.n_segment;
+        let dof = T::from_usize(2 * self.tapers.len() * k).unwrap()
+            / overlap_correlation(&self.tapers, k, welch.overlap_idx);
+        welch.amplitude_spectrum(
+            welch.scaling,
+            welch.to_periodogram(welch.calibrated(values)).with_dof(dof),
+        )
+    }
+    // Spectral density of the eigenspectra combined with the Thomson adaptive weights
+    fn adaptive_weighting(&self) -> Vec<T> {
+        let eigenspectra = self.eigenspectra();
+        let lambda: Vec<T> = self.tapers.iter().map(|t| t.concentration()).collect();
+        let n = self.welch.n_bins();
+        // initial estimate from the eigenspectra of the 2 first tapers
+        let k = eigenspectra.len().min(2);
+        let u = T::from_usize(k).unwrap().recip();
+        let mut density: Vec<T> = (0..n)
+            .map(|i| eigenspectra[..k].iter().fold(T::zero(), |s, e| s + e[i]) * u)
+            .collect();
+        let s2 = density.iter().fold(T::zero(), |s, &x| s + x) / T::from_usize(n).unwrap();
+        let tolerance = T::from_f64(1e-8).unwrap();
+        density.iter_mut().enumerate().for_each(|(i, s)| {
+            for _ in 0..100 {
+                let (num, den) = eigenspectra.iter().zip(&lambda).fold(
+                    (T::zero(), T::zero()),
+                    |(num, den), (e, &l)| {
+                        let d = l.sqrt() * *s / (l * *s + (T::one() - l) * s2);
+                        let d2 = d * d;
+                        (num + d2 * e[i], den + d2)
+                    },
+                );
+                if den <= T::zero() {
+                    break;
+                }
+                let next = num / den;
+                let converged = (next - *s).abs() <= tolerance * next;
+                *s = next;
+                if converged {
+                    break;
+                }
+            }
+        });
+        density
+    }
+}
+// Correlation factor `1+2 sum_{j=1}^{k-1} (1-j/k) rho(j)` of the degrees of freedom of the multitaper estimates
+// of `k` segments `d` samples apart, `rho(j)=sum_ab r_ab(j)^2/K` being the correlation of the estimates of segments `j` apart
+// and `r_ab(j)` the correlation of the taper `a` with the taper `b` shifted by `j d` samples
+fn overlap_correlation<T: Signal>(tapers: &[Dpss<T>], k: usize, d: usize) -> T {
+    let n = tapers[0].weights().len();
+    let sqr_sum: Vec<T> = tapers.iter().map(|w| w.sqr_sum()).collect();
+    let n_taper = T::from_usize(tapers.len()).unwrap();
+    let s = (1..k)
+        .take_while(|j| j * d < n)
+        .map(|j| {
+            let rho = tapers
+                .iter()
+                .zip(&sqr_sum)
+                .flat_map(|(a, &sa)| {
+                    tapers.iter().zip(&sqr_sum).map(move |(b, &sb)| {
+                        let r = a
+                            .weights()
+                            .iter()
+                            .zip(&b.weights()[j * d..])
+                            .map(|(&a, &b)| a * b)
+                            .sum::<T>();
+                        r * r / (sa * sb)
+                    })
+                })
+                .sum::<T>()
+                / n_taper;
+            (T::one() - T::from_usize(j).unwrap() / T::from_usize(k).unwrap()) * rho
+        })
+        .sum::<T>();
+    T::one() + s + s
+}
+// Returns `k` tapers of orders `0` to `k-1` with the size and time-bandwidth product of `window`
+fn tapers<T: Signal>(window: &Dpss<T>, k: usize, symmetry: Symmetry) -> Vec<Dpss<T>> {
+    (0..k)
+        .map(|order| window.with_order(order, symmetry))
+        .collect()
+}
+impl<'a, T: Signal, S: Sample<T>> Build<Multitaper<'a, T, S>> for Builder<'a, T, Dpss<T>, S> {
+    fn build(&self) -> Multitaper<'a, T, S> {
+        let welch: WelchDpss<'a, T, S> = self.build();
+        let k = (T::from_f64(2.).unwrap() * welch.window.nw())
+            .to_usize()
+            .unwrap()
+            .saturating_sub(1)
+            .max(1);
+        Multitaper {
+            tapers: tapers(&welch.window, k, self.symmetry),
+            symmetry: self.symmetry,
+            welch,
+            adaptive: false,
+        }
+    }
+}
+impl<'a, T: Signal, S: Sample<T>> Deref for Multitaper<'a, T, S> {
+    type Target = WelchDpss<'a, T, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.welch
+    }
+}
+impl<'a, T: Signal, S: Sample<T>> Display for Multitaper<'a, T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.welch.fmt(f)?;
+        write!(f, "\n - number of taper  : {:>6}", self.tapers.len())?;
+        write!(f, "\n - adaptive weights : {:>6}", self.adaptive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    fn white_noise(n: usize, sigma: f64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(n as u64);
+        (0..n)
+            .map(|_| {
+                let e: f64 = StandardNormal.sample(&mut rng);
+                sigma * e
+            })
+            .collect()
+    }
+
+    #[test]
+    fn white_noise_level() {
+        let (fs, sigma) = (10., 2.);
+        let x = white_noise(10_000, sigma);
+        let level = 2. * x.iter().map(|x| x * x).sum::<f64>() / x.len() as f64 / fs;
+        for n_segment in [1, 4] {
+            let mt: Multitaper<f64> = Multitaper::builder(&x, fs).n_segment(n_segment).build();
+            let periodogram = mt.periodogram();
+            let p = &periodogram[10..periodogram.len() - 10];
+            let mean = p.iter().sum::<f64>() / p.len() as f64;
+            assert!((mean / level - 1.).abs() < 1e-2, "{mean} vs {level}");
+        }
+    }
+
+    #[test]
+    fn degrees_of_freedom() {
+        // the relative variance of a chi-squared estimate is 2/dof,
+        // the variance being estimated over the realizations at bins further apart than the bandwidth
+        let mut rng = StdRng::seed_from_u64(28);
+        for n_segment in [1, 3] {
+            let (mut dof, mut sum, mut sqr_sum) = (0., vec![0.; 16], vec![0.; 16]);
+            let n = 400;
+            for _ in 0..n {
+                let x: Vec<f64> = (0..512).map(|_| StandardNormal.sample(&mut rng)).collect();
+                let mt: Multitaper<f64> = Multitaper::builder(&x, 1.).n_segment(n_segment).build();
+                let periodogram = mt.periodogram();
+                dof = periodogram.degrees_of_freedom().unwrap();
+                for (i, (s, q)) in sum.iter_mut().zip(&mut sqr_sum).enumerate() {
+                    let p = periodogram[periodogram.len() * (2 * i + 1) / 34];
+                    *s += p;
+                    *q += p * p;
+                }
+            }
+            let n = n as f64;
+            let var = sum
+                .iter()
+                .zip(&sqr_sum)
+                .map(|(s, q)| (q / n - (s / n).powi(2)) / (s / n).powi(2))
+                .sum::<f64>()
+                / sum.len() as f64;
+            assert!(
+                (var * dof / 2. - 1.).abs() < 0.1,
+                "k={n_segment}: {var} vs {}",
+                2. / dof
+            );
+        }
+    }
+
+    #[test]
+    fn tone() {
+        let (fs, f0, a) = (10., 1.25, 1.);
+        let x: Vec<f64> = (0..10_000)
+            .map(|i| a * (2. * std::f64::consts::PI * f0 * i as f64 / fs).sin())
+            .collect();
+        let mt: Multitaper<f64> = Multitaper::builder(&x, fs).build();
+        let periodogram = mt.periodogram();
+        let (f, _) = periodogram.max().unwrap();
+        assert!((f - f0).abs() <= periodogram.df(), "{f} vs {f0}");
+        let p = periodogram.total_power();
+        assert!((p / (0.5 * a * a) - 1.).abs() < 1e-2, "{p}");
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{chunks::Samples, Sample, Signal, WelchError};
+
+/// Handling of the NaN samples of the signal
+///
+/// A complex sample is NaN if either its real or its imaginary part is NaN.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NanPolicy {
+    /// the NaN samples are processed as any other samples and propagate into the periodogram
+    #[default]
+    Propagate,
+    /// a signal with NaN samples is rejected with [WelchError::NanSample](crate::WelchError::NanSample),
+    /// except for the NaN samples within the gaps of the signal (see [Builder::mask](crate::Builder::mask))
+    Error,
+    /// the segments with NaN samples are skipped,
+    /// the periodogram being averaged over the remaining segments
+    Skip,
+    /// the NaN samples are replaced by the linear interpolation of the nearest valid samples,
+    /// or by the nearest valid sample before the first and after the last valid samples
+    Interpolate,
+}
+
+// `true` if the sample is NaN
+pub(crate) fn is_nan<T: Signal, S: Sample<T>>(x: &S) -> bool {
+    let z = x.to_complex();
+    z.re.is_nan() || z.im.is_nan()
+}
+// Index of the first NaN sample, the invalid samples according to the validity `mask` being ignored
+pub(crate) fn first_nan<T: Signal, S: Sample<T>>(
+    signal: &Samples<S>,
+    mask: Option<&[bool]>,
+) -> Option<usize> {
+    signal
+        .iter()
+        .enumerate()
+        .position(|(i, x)| mask.is_none_or(|m| m[i]) && is_nan(x))
+}
+// Replaces the NaN samples by the linear interpolation of the nearest valid samples
+//
+// Returns `None` if all the samples are NaN
+pub(crate) fn interpolate_nan<T: Signal, S: Sample<T>>(signal: &Samples<S>) -> Option<Vec<S>> {
+    let mut x: Vec<S> = signal.iter().cloned().collect();
+    let valid: Vec<usize> = (0..x.len()).filter(|&i| !is_nan(&x[i])).collect();
+    let (&first, &last) = (valid.first()?, valid.last()?);
+    let (a, b) = (x[first], x[last]);
+    x[..first].iter_mut().for_each(|x| *x = a);
+    x[last + 1..].iter_mut().for_each(|x| *x = b);
+    valid.windows(2).filter(|v| v[1] > v[0] + 1).for_each(|v| {
+        let (i, j) = (v[0], v[1]);
+        let (a, b) = (x[i].to_complex(), x[j].to_complex());
+        let d = T::from_usize(j - i).unwrap();
+        (i + 1..j).for_each(|k| {
+            let w = T::from_usize(k - i).unwrap() / d;
+            x[k] = S::from_complex(a + (b - a) * w);
+        });
+    });
+    Some(x)
+}
+// Validity of the segments of `size` samples starting every `step` samples of a signal decimated by `factor`
+//
+// A segment is invalid if any sample it depends on is `missing`, including the samples within the span
+// of the decimation filter
+fn valid_segments(
+    missing: impl Iterator<Item = bool>,
+    size: usize,
+    step: usize,
+    factor: usize,
+) -> Vec<bool> {
+    // number of missing samples before each sample
+    let mut count = vec![0usize];
+    missing.for_each(|m| count.push(count[count.len() - 1] + m as usize));
+    let n = count.len() - 1;
+    let half = if factor > 1 { 10 * factor } else { 0 };
+    let n_decimated = n.div_ceil(factor.max(1));
+    (0..)
+        .map(|j| j * step)
+        .take_while(|&s| size > 0 && s + size <= n_decimated)
+        .map(|s| {
+            let lo = (s * factor).saturating_sub(half);
+            let hi = ((s + size - 1) * factor + half + 1).min(n);
+            count[hi] == count[lo]
+        })
+        .collect()
+}
+// Validity of the segments of `size` samples starting every `step` samples of the `signal` decimated by `factor`
+//
+// The segments with invalid samples according to the validity `mask` or, if `skip_nan` is `true`, with NaN samples
+// are invalid, all the segments being valid if `None` is returned
+pub(crate) fn segments_validity<T: Signal, S: Sample<T>>(
+    signal: &Samples<S>,
+    mask: Option<&[bool]>,
+    skip_nan: bool,
+    (size, step, factor): (usize, usize, usize),
+) -> Option<Vec<bool>> {
+    if mask.is_none() && !skip_nan {
+        return None;
+    }
+    // a signal without samples has no NaN sample
+    let mut samples = signal.iter();
+    let missing = (0..signal.len()).map(|i| {
+        let nan = samples.next().is_some_and(|x| skip_nan && is_nan(x));
+        mask.is_some_and(|m| !m[i]) || nan
+    });
+    Some(valid_segments(missing, size, step, factor))
+}
+impl NanPolicy {
+    // Checks the `signal` against the policy and the validity `mask`, the signal being split into segments of `size`
+    // samples starting every `step` samples of the signal decimated by `factor`
+    pub(crate) fn check<T: Signal, S: Sample<T>>(
+        &self,
+        signal: &Samples<S>,
+        mask: Option<&[bool]>,
+        segments: (usize, usize, usize),
+    ) -> Result<(), WelchError> {
+        match self {
+            NanPolicy::Error => {
+                if let Some(i) = first_nan(signal, mask) {
+                    return Err(WelchError::NanSample(i));
+                }
+            }
+            NanPolicy::Interpolate if signal.has_samples() && signal.iter().all(is_nan) => {
+                return Err(WelchError::NoValidSegment);
+            }
+            _ => (),
+        }
+        match segments_validity(signal, mask, *self == NanPolicy::Skip, segments) {
+            Some(valid) if !valid.contains(&true) => Err(WelchError::NoValidSegment),
+            _ => Ok(()),
+        }
+    }
+    // Checks the second signal `y` of cross spectral estimates against the policy and the validity `mask`,
+    // at least one segment being valid for both the signals `x` and `y`
+    pub(crate) fn check_cross<T: Signal, S: Sample<T>>(
+        &self,
+        x: &Samples<S>,
+        y: &Samples<S>,
+        mask: Option<&[bool]>,
+        segments: (usize, usize, usize),
+    ) -> Result<(), WelchError> {
+        self.check(y, mask, segments)?;
+        let skip_nan = *self == NanPolicy::Skip;
+        match (
+            segments_validity(x, mask, skip_nan, segments),
+            segments_validity(y, mask, skip_nan, segments),
+        ) {
+            (Some(vx), Some(vy)) if !vx.iter().zip(&vy).any(|(&a, &b)| a && b) => {
+                Err(WelchError::NoValidSegment)
+            }
+            _ => Ok(()),
+        }
+    }
+    // Applies the policy to the `signal`, filling the NaN samples with the interpolation policy
+    //
+    // Panics if a valid sample according to the validity `mask` is NaN with the error policy
+    pub(crate) fn apply<'a, T: Signal, S: Sample<T>>(
+        &self,
+        signal: &Samples<'a, S>,
+        mask: Option<&[bool]>,
+    ) -> Samples<'a, S> {
+        match self {
+            NanPolicy::Error => {
+                if let Some(i) = first_nan(signal, mask) {
+                    panic!("{}", WelchError::NanSample(i))
+                }
+                signal.clone()
+            }
+            NanPolicy::Interpolate if first_nan(signal, None).is_some() => {
+                interpolate_nan(signal).map_or_else(|| signal.clone(), Samples::from)
+            }
+            _ => signal.clone(),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{periodogram::chi_squared_quantile, Periodogram, Signal};
+
+/// Noise floor estimation method
+///
+/// The [Median](NoiseFloor::Median) and the [Percentile](NoiseFloor::Percentile) are insensitive to
+/// the tones of the signal, whereas the [Mean](NoiseFloor::Mean) is unbiased for a periodogram without tones.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum NoiseFloor {
+    /// mean of the periodogram values
+    #[default]
+    Mean,
+    /// median of the periodogram values, scaled to the mean if the degrees of freedom of the periodogram are known
+    Median,
+    /// percentile of the periodogram values, given as a fraction within `[0,1]`
+    Percentile(f64),
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the noise floor of the periodogram and its standard error
+    ///
+    /// If `peak_prominence` is given, the peaks with at least this prominence are excluded,
+    /// together with the bins on both sides of each peak down to the first local minimum (see [Periodogram::peaks]).
+    ///
+    /// The standard error of the [Mean](NoiseFloor::Mean) is the standard deviation of the values divided by the square root
+    /// of their number, whereas the standard error of the [Median](NoiseFloor::Median) and of the [Percentile](NoiseFloor::Percentile)
+    /// `q` of `n` values is half the difference between the percentiles `q-dq` and `q+dq`, with `dq=sqrt(q(1-q)/n)`.
+    /// The standard errors are underestimated if the neighboring bins are correlated, e.g. by the window.
+    pub fn noise_floor(&self, method: NoiseFloor, peak_prominence: Option<T>) -> (T, T) {
+        let mut values = match peak_prominence {
+            Some(prominence) => self.without_peaks(prominence),
+            None => self.to_vec(),
+        };
+        let n = values.len();
+        assert!(n > 0, "the noise floor requires a non-empty periodogram");
+        let q = match method {
+            NoiseFloor::Mean => {
+                let nt = T::from_usize(n).unwrap();
+                let mean = values.iter().cloned().sum::<T>() / nt;
+                let var = values.iter().map(|&x| (x - mean).powi(2)).sum::<T>()
+                    / T::from_usize(n.saturating_sub(1).max(1)).unwrap();
+                return (mean, (var / nt).sqrt());
+            }
+            NoiseFloor::Median => 0.5,
+            NoiseFloor::Percentile(q) => q,
+        };
+        assert!(
+            (0f64..=1.).contains(&q),
+            "the noise floor percentile must be within [0,1]"
+        );
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let percentile = |q: f64| values[((q * (n - 1) as f64).round() as usize).min(n - 1)];
+        let dq = (q * (1. - q) / n as f64).sqrt();
+        let level = percentile(q);
+        let error = (percentile((q + dq).min(1.)) - percentile((q - dq).max(0.)))
+            * T::from_f64(0.5).unwrap();
+        match self.degrees_of_freedom() {
+            // median of a chi-squared distribution with v degrees of freedom scaled to its mean v
+            Some(dof) if method == NoiseFloor::Median => {
+                let v = dof.to_f64().unwrap();
+                let u = dof / T::from_f64(chi_squared_quantile(0.5, v)).unwrap();
+                (level * u, error * u)
+            }
+            _ => (level, error),
+        }
+    }
+    // Periodogram values without the peaks with at least the given prominence
+    fn without_peaks(&self, prominence: T) -> Vec<T> {
+        let p: &[T] = self;
+        let n = p.len();
+        let mut keep = vec![true; n];
+        for peak in self.peaks(T::neg_infinity(), prominence) {
+            let (mut lo, mut hi) = (peak.bin, peak.bin);
+            while lo > 0 && p[lo - 1] < p[lo] {
+                lo -= 1;
+            }
+            while hi + 1 < n && p[hi + 1] < p[hi] {
+                hi += 1;
+            }
+            keep[lo..=hi].iter_mut().for_each(|k| *k = false);
+        }
+        p.iter()
+            .zip(keep)
+            .filter(|(_, keep)| *keep)
+            .map(|(&x, _)| x)
+            .collect()
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{Periodogram, Signal};
+
+/// Octave ratio of the fractional-octave bands
+///
+/// The octave ratio `G` is either `2` or `10^(3/10)` according to IEC 61260
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum OctaveBase {
+    /// base-2 octave ratio `G=2`
+    Two,
+    /// base-10 octave ratio `G=10^(3/10)`
+    #[default]
+    Ten,
+}
+impl OctaveBase {
+    // Octave ratio
+    fn ratio(&self) -> f64 {
+        match self {
+            OctaveBase::Two => 2.,
+            OctaveBase::Ten => 10f64.powf(0.3),
+        }
+    }
+}
+/// Fractional-octave band
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OctaveBand<T> {
+    /// band exact mid-band frequency `[Hz]`
+    pub center: T,
+    /// band lower edge frequency `[Hz]`
+    pub lower: T,
+    /// band upper edge frequency `[Hz]`
+    pub upper: T,
+    /// signal power within the band
+    pub power: T,
+}
+impl<T: Signal> OctaveBand<T> {
+    /// Returns the band level in dB relative to `reference`, `10log10(power/reference)`
+    pub fn level(&self, reference: T) -> T {
+        T::from_f64(10.).unwrap() * (self.power / reference).log10()
+    }
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the `1/fraction`-octave bands of the spectral density
+    ///
+    /// The exact mid-band frequencies of the bands follow IEC 61260, with the reference frequency `1000Hz`
+    /// and the octave ratio `G` given by `base`:
+    /// `1000G^(x/b)` for an odd `b=fraction` or `1000G^((2x+1)/(2b))` for an even `b`, `x` being an integer,
+    /// and the band edges are the mid-band frequencies multiplied by `G^(-1/(2b))` and `G^(1/(2b))`.
+    /// The power within a band is the integral of the spectral density, each frequency bin contributing
+    /// in proportion of its overlap with the band.
+    /// Only the bands between the upper edge of the zero frequency bin, or the lower edge of the first bin, and the last frequency are returned.
+    pub fn octave_bands(&self, fraction: usize, base: OctaveBase) -> Vec<OctaveBand<T>> {
+        assert!(fraction > 0, "the octave fraction must be positive");
+        assert!(
+            !self.is_two_sided(),
+            "the octave bands require a one-sided periodogram"
+        );
+        let df = self.df().to_f64().unwrap();
+        let f0 = self.f0().to_f64().unwrap();
+        let f_min = (f0 - 0.5 * df).max(0.5 * df);
+        let f_max = f0 + (self.len() - 1) as f64 * df;
+        let g = base.ratio();
+        let b = fraction as f64;
+        let center = |x: i32| {
+            if fraction % 2 == 1 {
+                1e3 * g.powf(x as f64 / b)
+            } else {
+                1e3 * g.powf((2 * x + 1) as f64 / (2. * b))
+            }
+        };
+        let half_band = g.powf(0.5 / b);
+        let x_min = (b * (f_min / 1e3).ln() / g.ln()).floor() as i32 - 1;
+        (x_min..)
+            .map(|x| {
+                let fm = center(x);
+                (fm, fm / half_band, fm * half_band)
+            })
+            .skip_while(|&(_, lower, _)| lower < f_min)
+            .take_while(|&(_, _, upper)| upper <= f_max)
+            .map(|(fm, lower, upper)| OctaveBand {
+                center: T::from_f64(fm).unwrap(),
+                lower: T::from_f64(lower).unwrap(),
+                upper: T::from_f64(upper).unwrap(),
+                power: self.band_power(lower, upper),
+            })
+            .collect()
+    }
+    // Integral of the periodogram within `[lower,upper]`
+    fn band_power(&self, lower: f64, upper: f64) -> T {
+        let df = self.df().to_f64().unwrap();
+        let f0 = self.f0().to_f64().unwrap();
+        let first = (((lower - f0) / df) - 0.5).floor().max(0.) as usize;
+        let last = ((((upper - f0) / df) + 0.5).ceil().max(0.) as usize).min(self.len() - 1);
+        (first..=last)
+            .map(|i| {
+                let f = f0 + i as f64 * df;
+                let overlap = ((f + 0.5 * df).min(upper) - (f - 0.5 * df).max(lower)).max(0.);
+                self[i] * T::from_f64(overlap).unwrap()
+            })
+            .sum()
+    }
+}
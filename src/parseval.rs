@@ -0,0 +1,123 @@
+use crate::{Periodogram, Sample, Signal, Welch, Window};
+
+impl<T: Signal> Periodogram<T> {
+    /// Returns the total power of the signal, the integral of the spectral density over the frequency bins
+    ///
+    /// The total power is the sum of `S(f) df`, `S` being the spectral density and `df` the width of the frequency bins
+    /// ([Periodogram::df]), and it is the mean square of the signal according to the Parseval theorem.
+    /// The negative frequencies are already accounted for in the positive frequencies of a one-sided periodogram.
+    ///
+    /// The periodogram is assumed to be a spectral density ([Scaling::Density](crate::Scaling::Density))
+    /// with the energy correction of the window ([WindowCorrection::Energy](crate::WindowCorrection::Energy)),
+    /// the default of the spectral density; see also [Welch::total_power].
+    pub fn total_power(&self) -> T {
+        let df = self.df();
+        self[..].iter().fold(T::zero(), |p, &x| p + x * df)
+    }
+    /// Returns the variance of the signal, the total power without the power of the zero frequency bin
+    ///
+    /// See [Periodogram::total_power]; the zero frequency bin is the signal mean squared.
+    /// The window spreads the mean over the bins next to the zero frequency,
+    /// so the signal is detrended ([Detrend::Constant](crate::Detrend::Constant)) for the variance to be exact.
+    pub fn variance(&self) -> T {
+        let p0 = self
+            .bin_of(T::zero())
+            .filter(|&i| {
+                (self.f0() / self.df() + T::from_usize(i).unwrap()).abs()
+                    < T::from_f64(1e-6).unwrap()
+            })
+            .map_or(T::zero(), |i| self[i] * self.df());
+        self.total_power() - p0
+    }
+}
+
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
+    // Spectral density with the energy correction of the window, whatever the scaling and the window correction
+    fn energy_density(&self) -> Periodogram<T> {
+        let u = (self.window.sqr_sum() * self.fs).recip();
+        self.to_periodogram(
+            self.calibrated(self.averaged_power().into_iter().map(|x| x * u).collect()),
+        )
+    }
+    /// Returns the total power of the signal (signal unit squared), the integral of the spectral density
+    ///
+    /// The spectral density is normalized with the energy correction of the window,
+    /// whatever the [Welch::scaling](Welch#structfield.scaling) and the [Welch::correction](Welch#structfield.correction)
+    /// of the estimator, such as the total power is the mean square of the signal (see [Periodogram::total_power])
+    pub fn total_power(&self) -> T {
+        self.energy_density().total_power()
+    }
+    /// Returns the variance of the signal (signal unit squared), the total power without the power of the zero frequency bin
+    ///
+    /// See [Welch::total_power] and [Periodogram::variance]
+    pub fn variance(&self) -> T {
+        self.energy_density().variance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Builder, Detrend, Hann, One, Welch};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Random signal of `n` samples with a mean of 1
+    fn signal(n: usize) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(n as u64);
+        (0..n).map(|_| 1. + rng.gen::<f64>() - 0.5).collect()
+    }
+    fn mean_square(x: &[f64]) -> f64 {
+        x.iter().map(|x| x * x).sum::<f64>() / x.len() as f64
+    }
+    fn variance(x: &[f64]) -> f64 {
+        let mean = x.iter().sum::<f64>() / x.len() as f64;
+        x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / x.len() as f64
+    }
+
+    #[test]
+    fn total_power() {
+        // odd and even DFT sizes, with and without zero padding, one-sided and two-sided
+        for (n, m) in [(1000, 1000), (999, 999), (1000, 1501), (999, 1024)] {
+            for two_sided in [false, true] {
+                let x = signal(n);
+                let welch: Welch<f64, One<f64>> = Builder::new(&x)
+                    .segment_size(n)
+                    .dft_size(m)
+                    .two_sided(two_sided)
+                    .sampling_frequency(10.)
+                    .build();
+                let (p, ms) = (welch.total_power(), mean_square(&x));
+                assert!((p - ms).abs() < 1e-10 * ms, "n={n}, m={m}: {p} vs {ms}");
+                let p = welch.periodogram().total_power();
+                assert!((p - ms).abs() < 1e-10 * ms, "n={n}, m={m}: {p} vs {ms}");
+            }
+        }
+    }
+
+    #[test]
+    fn variance_of_detrended_segment() {
+        for (n, m) in [(1000, 1000), (999, 999), (1000, 1501), (999, 1024)] {
+            for two_sided in [false, true] {
+                let x = signal(n);
+                let welch: Welch<f64, One<f64>> = Builder::new(&x)
+                    .segment_size(n)
+                    .dft_size(m)
+                    .two_sided(two_sided)
+                    .detrend(Detrend::Constant)
+                    .build();
+                let (v, var) = (welch.variance(), variance(&x));
+                assert!((v - var).abs() < 1e-10 * var, "n={n}, m={m}: {v} vs {var}");
+            }
+        }
+    }
+
+    #[test]
+    fn variance_of_averaged_segments() {
+        let x = signal(100_000);
+        let welch: Welch<f64, Hann<f64>> = Builder::new(&x)
+            .n_segment(32)
+            .detrend(Detrend::Constant)
+            .build();
+        let (v, var) = (welch.variance(), variance(&x));
+        assert!((v - var).abs() < 0.02 * var, "{v} vs {var}");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{Periodogram, Signal};
+
+/// Periodogram peak
+///
+/// The frequency and the value of the peak are interpolated between the periodogram bins
+/// (see [Periodogram::peaks])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Peak<T> {
+    /// index of the periodogram bin of the peak
+    pub bin: usize,
+    /// interpolated frequency `[Hz]`
+    pub frequency: T,
+    /// interpolated periodogram value
+    pub value: T,
+    /// height of the peak above the highest of the lowest values on each side of the peak,
+    /// before reaching a higher peak or the edge of the periodogram
+    pub prominence: T,
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the peaks of the periodogram above `threshold` with a prominence greater or equal to `prominence`
+    ///
+    /// A peak is a bin with a value greater than the value of the previous bin and greater or equal
+    /// to the value of the next bin, the first and last bins are never peaks.
+    /// The peak frequency and value are interpolated with the parabola through the logarithm of the
+    /// values of the peak bin and of its 2 neighbors, which is exact for a Gaussian shaped peak
+    /// and a good approximation of the main lobe of most windows.
+    /// The peaks are ordered by increasing frequency.
+    pub fn peaks(&self, threshold: T, prominence: T) -> Vec<Peak<T>> {
+        let p: &[T] = self;
+        let n = p.len();
+        let frequency = self.frequency();
+        let df = self.df();
+        (1..n.saturating_sub(1))
+            .filter(|&i| p[i] > p[i - 1] && p[i] >= p[i + 1] && p[i] > threshold)
+            .filter_map(|i| {
+                let peak_prominence = peak_prominence(p, i);
+                if peak_prominence < prominence {
+                    return None;
+                }
+                let (delta, value) = interpolate(p[i - 1], p[i], p[i + 1]);
+                Some(Peak {
+                    bin: i,
+                    frequency: frequency[i] + delta * df,
+                    value,
+                    prominence: peak_prominence,
+                })
+            })
+            .collect()
+    }
+}
+// Prominence of the peak at bin `i`
+fn peak_prominence<T: Signal>(p: &[T], i: usize) -> T {
+    let base = |iter: &mut dyn Iterator<Item = &T>| {
+        iter.take_while(|&&x| x <= p[i])
+            .fold(p[i], |a, &x| a.min(x))
+    };
+    let left = base(&mut p[..i].iter().rev());
+    let right = base(&mut p[i + 1..].iter());
+    p[i] - left.max(right)
+}
+// Offset in bins from the center bin and value of the vertex of the parabola through `(a,b,c)`
+//
+// The parabola is fitted to the logarithm of the values if they are all positive
+fn interpolate<T: Signal>(a: T, b: T, c: T) -> (T, T) {
+    let half = T::from_f64(0.5).unwrap();
+    let quarter = T::from_f64(0.25).unwrap();
+    let log = a > T::zero() && c > T::zero();
+    let (a, b, c) = if log {
+        (a.ln(), b.ln(), c.ln())
+    } else {
+        (a, b, c)
+    };
+    let curvature = a - b - b + c;
+    if curvature.is_zero() {
+        let value = if log { b.exp() } else { b };
+        return (T::zero(), value);
+    }
+    let delta = half * (a - c) / curvature;
+    let vertex = b - quarter * (a - c) * delta;
+    (delta, if log { vertex.exp() } else { vertex })
+}
@@ -1,67 +1,521 @@
-use crate::{Signal, Welch, Window};
-use std::ops::Deref;
+use crate::{Averaging, Sample, Scaling, Signal, Welch, Window};
+use num_complex::Complex;
+use std::ops::{Add, Deref};
 
 /// Signal periodogram
+///
+/// The periodogram values are either real (`V=T`) or complex (`V=Complex<T>`) for cross spectra.
+/// The periodogram of a real signal is one-sided, with only the positive frequencies,
+/// whereas the periodogram of a complex signal is two-sided, with frequencies ordered from `-fs/2` to `fs/2`.
+///
+/// With the `serde` feature, the periodogram is serialized and deserialized together with
+/// its sampling frequency, its frequency grid and its degrees of freedom.
 #[derive(Debug, Clone)]
-pub struct Periodogram<T: Signal>(T, Vec<T>);
-impl<T: Signal> Deref for Periodogram<T> {
-    type Target = [T];
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Periodogram<T: Signal, V = T> {
+    fs: T,
+    f0: T,
+    df: T,
+    values: Vec<V>,
+    two_sided: bool,
+    dof: Option<T>,
+}
+impl<T: Signal, V> Deref for Periodogram<T, V> {
+    type Target = [V];
 
     fn deref(&self) -> &Self::Target {
-        self.1.as_slice()
+        self.values.as_slice()
     }
 }
 impl<T: Signal> Periodogram<T> {
-    /// Creates a new [Periodogram] from [Welch::periodogram] scaled with `u`
-    fn new<W: Window<T>>(welch: &Welch<T, W>, u: T) -> Self {
-        let n = welch.dft_size / 2;
-        Self(
-            welch.fs,
-            welch
-                .dfts()
-                .chunks(welch.dft_size)
-                .map(|dft| dft.iter().take(n).map(|x| x.norm_sqr()).collect::<Vec<T>>())
-                .fold(vec![T::zero(); n], |mut a, p| {
-                    a.iter_mut().zip(p).for_each(|(a, p)| *a += p);
-                    a
-                })
-                .into_iter()
-                .map(|x| x * u)
-                .collect(),
-        )
+    /// Creates a new [Periodogram] from the [Averaging] of the [Welch] segments periodogram scaled with `u`
+    fn new<W: Window<T>, S: Sample<T>>(welch: &Welch<T, W, S>, u: T) -> Self {
+        let periodogram = welch.to_periodogram(
+            welch.calibrated(welch.averaged_power().into_iter().map(|x| x * u).collect()),
+        );
+        if welch.averaging == Averaging::Mean {
+            periodogram.with_dof(welch.degrees_of_freedom())
+        } else {
+            periodogram
+        }
+    }
+    // Creates the [Periodogram] of each [Welch] segment scaled with `u`
+    fn segments<W: Window<T>, S: Sample<T>>(welch: &Welch<T, W, S>, u: T) -> Vec<Self> {
+        welch
+            .segments_periodogram()
+            .into_iter()
+            .map(|p| welch.to_periodogram(welch.calibrated(p.into_iter().map(|x| x * u).collect())))
+            .collect()
+    }
+}
+impl<T: Signal, V> Periodogram<T, V> {
+    /// Creates a new [Periodogram] from the signal sampling frequency `fs`, the size of the discrete Fourier transform
+    /// `dft_size` and the periodogram `values`
+    pub(crate) fn from_values(fs: T, dft_size: usize, values: Vec<V>) -> Self {
+        Self {
+            fs,
+            f0: T::zero(),
+            df: fs / T::from_usize(dft_size).unwrap(),
+            values,
+            two_sided: false,
+            dof: None,
+        }
+    }
+    /// Creates a new two-sided [Periodogram] from the signal sampling frequency `fs` and the periodogram `values`
+    ///
+    /// The `values` are given in the order of the discrete Fourier transform and are shifted such as
+    /// the zero frequency is at the center of the periodogram
+    pub(crate) fn two_sided(fs: T, mut values: Vec<V>) -> Self {
+        let n = values.len();
+        values.rotate_right(n / 2);
+        let df = fs / T::from_usize(n).unwrap();
+        Self {
+            fs,
+            f0: -T::from_usize(n / 2).unwrap() * df,
+            df,
+            values,
+            two_sided: true,
+            dof: None,
+        }
+    }
+    /// Creates a new [Periodogram] from the signal sampling frequency `fs` and the periodogram `values`
+    /// at the frequencies `f0+i df`
+    pub(crate) fn from_band(fs: T, f0: T, df: T, values: Vec<V>, two_sided: bool) -> Self {
+        Self {
+            fs,
+            f0,
+            df,
+            values,
+            two_sided,
+            dof: None,
+        }
+    }
+    // Sets the equivalent degrees of freedom of the periodogram estimate
+    pub(crate) fn with_dof(self, dof: T) -> Self {
+        Self {
+            dof: Some(dof),
+            ..self
+        }
+    }
+    /// Returns the equivalent degrees of freedom of the periodogram estimate, if known
+    ///
+    /// The degrees of freedom of a [Welch] periodogram are only known if the segments are averaged with [Averaging::Mean]
+    pub fn degrees_of_freedom(&self) -> Option<T> {
+        self.dof
+    }
+    /// Returns `true` if the periodogram is two-sided
+    pub fn is_two_sided(&self) -> bool {
+        self.two_sided
+    }
+    // Creates a new [Periodogram] with the same frequencies than `self` and the given `values`
+    pub(crate) fn with_values<U>(&self, values: Vec<U>) -> Periodogram<T, U> {
+        Periodogram {
+            fs: self.fs,
+            f0: self.f0,
+            df: self.df,
+            values,
+            two_sided: self.two_sided,
+            dof: None,
+        }
+    }
+    /// Returns the signal sampling frequency in Hz
+    pub fn fs(&self) -> T {
+        self.fs
+    }
+    /// Returns the width of the frequency bins in Hz, `fs/m` for the discrete Fourier transform of size `m`
+    pub fn df(&self) -> T {
+        self.df
+    }
+    // Frequency of the first bin in Hz
+    pub(crate) fn f0(&self) -> T {
+        self.f0
     }
     /// Returns the frequency vector in Hz
+    ///
+    /// The frequencies are the discrete Fourier transform frequencies `i fs/m`,
+    /// with `i` within `[-m/2,m/2)` for two-sided periodograms,
+    /// or the frequencies `f1+i df` of a band starting at `f1` (see [Welch::zoom])
     pub fn frequency(&self) -> Vec<T> {
-        let n = self.1.len();
-        let fs = self.0;
-        (0..n)
-            .map(|i| {
-                T::from_usize(i).unwrap() * fs * T::from_f32(0.5).unwrap()
-                    / T::from_usize(n - 1).unwrap()
-            })
+        (0..self.values.len())
+            .map(|i| self.f0 + T::from_usize(i).unwrap() * self.df)
             .collect()
     }
+    /// Returns the index of the frequency bin nearest to the frequency `f` `[Hz]`
+    ///
+    /// The bins are centered on the periodogram frequencies (see [Periodogram::frequency]) and they are `df` wide.
+    /// Returns `None` if `f` is outside the bins or if `f` is `NaN`.
+    pub fn bin_of(&self, f: T) -> Option<usize> {
+        let x = ((f - self.f0) / self.df).round();
+        if x.is_nan() || x < T::zero() {
+            return None;
+        }
+        x.to_usize().filter(|&i| i < self.values.len())
+    }
+    /// Returns the periodogram value of the frequency bin nearest to the frequency `f` `[Hz]` (see [Periodogram::bin_of])
+    ///
+    /// The value interpolated between the frequency bins is given by [Periodogram::interpolate_at]
+    pub fn value_at(&self, f: T) -> Option<V>
+    where
+        V: Copy,
+    {
+        self.bin_of(f).map(|i| self.values[i])
+    }
+    /// Returns an iterator over the pairs of frequency in Hz (see [Periodogram::frequency]) and periodogram value
+    ///
+    /// The values alone are iterated over from the slice of values, e.g. `periodogram[..].iter()`
+    pub fn iter(&self) -> impl Iterator<Item = (T, V)> + '_
+    where
+        V: Copy,
+    {
+        self.values
+            .iter()
+            .enumerate()
+            .map(move |(i, &v)| (self.f0 + T::from_usize(i).unwrap() * self.df, v))
+    }
+    /// Returns the signal sampling frequency in Hz and the periodogram values, consuming the periodogram
+    pub fn into_inner(self) -> (T, Vec<V>) {
+        (self.fs, self.values)
+    }
+}
+impl<T: Signal> Periodogram<T> {
+    /// Returns the lower and upper bounds of the `1-alpha` confidence interval of the periodogram
+    ///
+    /// The periodogram estimate `P` is assumed to follow a chi-squared distribution with `v` equivalent
+    /// degrees of freedom, such as the confidence interval is `[vP/X2(1-alpha/2),vP/X2(alpha/2)]`,
+    /// where `X2(p)` is the chi-squared quantile of probability `p`.
+    /// Returns `None` if the degrees of freedom of the estimate are unknown.
+    pub fn confidence_interval(&self, alpha: f64) -> Option<(Periodogram<T>, Periodogram<T>)> {
+        assert!(
+            alpha > 0. && alpha < 1.,
+            "the confidence interval alpha must be within (0,1)"
+        );
+        let dof = self.dof?;
+        let v = dof.to_f64().unwrap();
+        let bound = |p: f64| {
+            let q = dof / T::from_f64(chi_squared_quantile(p, v)).unwrap();
+            Periodogram {
+                dof: self.dof,
+                ..self.with_values(self.values.iter().map(|&x| x * q).collect())
+            }
+        };
+        Some((bound(1. - 0.5 * alpha), bound(0.5 * alpha)))
+    }
+    /// Returns the periodogram in decibels relative to `reference`, `10log10(P/reference)`
+    ///
+    /// Values of zero, or less, are clamped to the smallest positive value of `T`,
+    /// such as the periodogram in decibels is always finite
+    pub fn to_db(&self, reference: T) -> Periodogram<T> {
+        assert!(
+            reference > T::zero(),
+            "the decibel reference must be positive"
+        );
+        let ten = T::from_f64(10.).unwrap();
+        self.with_values(
+            self.values
+                .iter()
+                .map(|&x| ten * (x.max(T::min_positive_value()) / reference).log10())
+                .collect(),
+        )
+    }
+    /// Returns the power spectrum in decibels relative to 1mW (dBm), the signal power being given in Watt
+    pub fn dbm(&self) -> Periodogram<T> {
+        self.to_db(T::from_f64(1e-3).unwrap())
+    }
+    /// Returns the spectral density in decibels relative to the signal unit squared per Hertz (dB/Hz)
+    pub fn db_hz(&self) -> Periodogram<T> {
+        self.to_db(T::one())
+    }
+    /// Returns the frequency in Hz and the value of the maximum of the periodogram
+    ///
+    /// The `NaN` values are ignored, and the first bin is returned if several bins have the maximum value.
+    /// Returns `None` if all the values are `NaN`.
+    pub fn max(&self) -> Option<(T, T)> {
+        self.max_in(T::neg_infinity(), T::infinity())
+    }
+    /// Returns the frequency in Hz and the value of the maximum of the periodogram within the frequency band `[f1,f2]` `[Hz]`
+    ///
+    /// See [Periodogram::max]; returns `None` if there is no frequency bin within the band.
+    pub fn max_in(&self, f1: T, f2: T) -> Option<(T, T)> {
+        self.iter()
+            .filter(|&(f, x)| f >= f1 && f <= f2 && !x.is_nan())
+            .fold(None, |max, (f, x)| match max {
+                Some((_, y)) if y >= x => max,
+                _ => Some((f, x)),
+            })
+    }
+    /// Returns the amplitude spectral density (signal unit per square root of Hertz), the square root of the spectral density
+    ///
+    /// The periodogram is assumed to be a spectral density ([Scaling::Density]).
+    /// The square root is taken bin-wise on the averaged periodogram, such as the amplitude spectral density
+    /// must be squared back before any further averaging.
+    /// Negative values are clamped to zero.
+    pub fn asd(&self) -> Periodogram<T> {
+        self.with_values(
+            self.values
+                .iter()
+                .map(|&x| x.max(T::zero()).sqrt())
+                .collect(),
+        )
+    }
+}
+// Quantile of the chi-squared distribution with `v` degrees of freedom for the probability `p`
+pub(crate) fn chi_squared_quantile(p: f64, v: f64) -> f64 {
+    // bisection of the cumulative distribution P(v/2,x/2)
+    let (mut lo, mut hi) = (0f64, v.max(1.));
+    while gamma_p(0.5 * v, 0.5 * hi) < p {
+        hi *= 2.;
+    }
+    for _ in 0..100 {
+        let x = 0.5 * (lo + hi);
+        if gamma_p(0.5 * v, 0.5 * x) < p {
+            lo = x;
+        } else {
+            hi = x;
+        }
+    }
+    0.5 * (lo + hi)
+}
+// Regularized lower incomplete gamma function P(a,x)
+fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    let ln_pre = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1. {
+        // series expansion
+        let (mut term, mut sum, mut ap) = (1. / a, 1. / a, a);
+        while term.abs() > sum.abs() * 1e-15 {
+            ap += 1.;
+            term *= x / ap;
+            sum += term;
+        }
+        sum * ln_pre.exp()
+    } else {
+        // continued fraction (modified Lentz)
+        let tiny = 1e-300;
+        let mut b = x + 1. - a;
+        let mut c = 1. / tiny;
+        let mut d = 1. / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.).abs() < 1e-15 {
+                break;
+            }
+        }
+        1. - ln_pre.exp() * h
+    }
+}
+// Logarithm of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    const C: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let ser = C
+        .iter()
+        .enumerate()
+        .fold(1.000000000190015, |s, (j, c)| s + c / (x + 1. + j as f64));
+    -tmp + (2.5066282746310005 * ser / x).ln()
+}
+impl<T: Signal> Periodogram<T, Complex<T>> {
+    /// Returns the phase of the complex periodogram in radians, within `[-pi,pi]`
+    pub fn phase(&self) -> Periodogram<T> {
+        self.with_values(self.values.iter().map(|x| x.arg()).collect())
+    }
+    /// Returns the magnitude of the complex periodogram
+    pub fn magnitude(&self) -> Periodogram<T> {
+        self.with_values(self.values.iter().map(|x| x.norm()).collect())
+    }
+}
+// Unbiased sample variance of the `segments` periodogram, `None` with less than 2 segments
+fn segment_variance<T: Signal>(segments: Vec<Periodogram<T>>) -> Option<Periodogram<T>> {
+    if segments.len() < 2 {
+        return None;
+    }
+    let k = T::from_usize(segments.len()).unwrap();
+    let n = segments[0].len();
+    let (sum, sqr_sum) = segments.iter().fold(
+        (vec![T::zero(); n], vec![T::zero(); n]),
+        |(mut s, mut s2), p| {
+            s.iter_mut()
+                .zip(s2.iter_mut())
+                .zip(p.values.iter())
+                .for_each(|((s, s2), &x)| {
+                    *s += x;
+                    *s2 += x * x;
+                });
+            (s, s2)
+        },
+    );
+    Some(
+        segments[0].with_values(
+            sum.into_iter()
+                .zip(sqr_sum)
+                .map(|(s, s2)| ((s2 - s * s / k) / (k - T::one())).max(T::zero()))
+                .collect(),
+        ),
+    )
+}
+// Standard error of the mean of the `segments` periodogram, `None` with less than 2 segments
+fn standard_error<T: Signal>(segments: Vec<Periodogram<T>>) -> Option<Periodogram<T>> {
+    let k = T::from_usize(segments.len()).unwrap();
+    let var = segment_variance(segments)?;
+    Some(var.with_values(var.values.iter().map(|&v| (v / k).sqrt()).collect()))
+}
+// Doubles the one-sided periodogram `values` of a discrete Fourier transform of size `m` to account
+// for the negative frequencies, except for the zero and Nyquist frequency bins that are not folded
+pub(crate) fn one_sided<V: Copy + Add<Output = V>>(values: &mut [V], m: usize) {
+    values
+        .iter_mut()
+        .enumerate()
+        .skip(1)
+        .filter(|(i, _)| 2 * i != m)
+        .for_each(|(_, v)| *v = *v + *v);
 }
 /// Interface to the spatial density periodogram
 pub trait SpectralDensityPeriodogram<T: Signal> {
     /// Returns the signal spectral density (signal unit squared per Hertz)
     fn periodogram(&self) -> Periodogram<T>;
+    /// Returns the spectral density of each segment, before averaging
+    fn segment_periodograms(&self) -> Vec<Periodogram<T>>;
+    /// Returns the sample variance of the segments spectral density, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    fn segment_variance(&self) -> Option<Periodogram<T>> {
+        segment_variance(self.segment_periodograms())
+    }
+    /// Returns the standard error of the mean of the segments spectral density, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    fn standard_error(&self) -> Option<Periodogram<T>> {
+        standard_error(self.segment_periodograms())
+    }
 }
 /// Interface to the power spectrum periodogram
 pub trait PowerSpectrumPeriodogram<T: Signal> {
     /// Returns the signal power spectrum (signal unit squared)
     fn periodogram(&self) -> Periodogram<T>;
+    /// Returns the power spectrum of each segment, before averaging
+    fn segment_periodograms(&self) -> Vec<Periodogram<T>>;
+    /// Returns the sample variance of the segments power spectrum, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    fn segment_variance(&self) -> Option<Periodogram<T>> {
+        segment_variance(self.segment_periodograms())
+    }
+    /// Returns the standard error of the mean of the segments power spectrum, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    fn standard_error(&self) -> Option<Periodogram<T>> {
+        standard_error(self.segment_periodograms())
+    }
 }
 
-impl<'a, T: Signal, W: Window<T>> SpectralDensityPeriodogram<T> for Welch<'a, T, W> {
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> SpectralDensityPeriodogram<T>
+    for Welch<'a, T, W, S>
+{
     fn periodogram(&self) -> Periodogram<T> {
-        let u = (self.window.sqr_sum() * T::from_usize(self.n_segment).unwrap() * self.fs).recip();
+        let u = self.scale(Scaling::Density);
         Periodogram::new(self, u)
     }
+    fn segment_periodograms(&self) -> Vec<Periodogram<T>> {
+        let u = self.scale(Scaling::Density);
+        Periodogram::segments(self, u)
+    }
 }
-impl<'a, T: Signal, W: Window<T>> PowerSpectrumPeriodogram<T> for Welch<'a, T, W> {
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> PowerSpectrumPeriodogram<T> for Welch<'a, T, W, S> {
     fn periodogram(&self) -> Periodogram<T> {
-        let u = (self.window.sum_sqr() * T::from_usize(self.n_segment).unwrap()).recip();
+        let u = self.scale(Scaling::Spectrum);
         Periodogram::new(self, u)
     }
+    fn segment_periodograms(&self) -> Vec<Periodogram<T>> {
+        let u = self.scale(Scaling::Spectrum);
+        Periodogram::segments(self, u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Build, Builder, Hann, One, Periodogram, Scaling, SpectralDensityPeriodogram, Welch,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    // Power spectrum of 2 tones between the frequency bins of width 1Hz
+    fn tones() -> Periodogram<f64> {
+        let fs = 1e3;
+        let signal: Vec<f64> = (0..8_000)
+            .map(|i| {
+                let t = i as f64 / fs;
+                let w = 2. * std::f64::consts::PI * t;
+                (100.5 * w).sin() + 0.1 * (300.25 * w).sin()
+            })
+            .collect();
+        let welch: Welch<f64, Hann<f64>> = Builder::new(&signal)
+            .sampling_frequency(fs)
+            .segment_size(1000)
+            .power_of_two_dft(false)
+            .scaling(Scaling::Spectrum)
+            .build();
+        welch.periodogram()
+    }
+
+    #[test]
+    fn max() {
+        let (f, x) = tones().max().unwrap();
+        assert!(f == 100. || f == 101., "frequency: {f}");
+        // the amplitude of a Hann windowed tone halfway between 2 bins is attenuated by 1.42dB
+        assert!((x / 0.5 - 10f64.powf(-0.142)).abs() < 1e-2, "value: {x}");
+    }
+
+    #[test]
+    fn max_in() {
+        let p = tones();
+        let (f, x) = p.max_in(200., 400.).unwrap();
+        assert_eq!(f, 300.);
+        // power ratio of the tones of 0.01, the tones being attenuated differently by the window
+        let ratio = x / p.max().unwrap().1;
+        assert!(ratio > 0.005 && ratio < 0.02, "ratio: {ratio}");
+        assert!(p.max_in(100.2, 100.8).is_none());
+    }
+
+    #[test]
+    fn segment_variance() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let signal: Vec<f64> = (0..256 * 400)
+            .map(|_| StandardNormal.sample(&mut rng))
+            .collect();
+        let welch: Welch<f64, One<f64>> =
+            Builder::new(&signal).segment_size(256).overlap(0.).build();
+        assert_eq!(welch.n_segment, 400);
+        let mean = welch.periodogram();
+        let var = SpectralDensityPeriodogram::segment_variance(&welch).unwrap();
+        // the periodogram of white noise at the frequencies other than 0 and fs/2 is P χ²(2)/2, of variance P²
+        let n = mean.len();
+        let ratio = (1..n - 1).map(|i| var[i] / mean[i].powi(2)).sum::<f64>() / (n - 2) as f64;
+        assert!((ratio - 1.).abs() < 0.05, "ratio: {ratio}");
+        let single: Welch<f64, One<f64>> = Builder::new(&signal[..256]).segment_size(256).build();
+        assert!(SpectralDensityPeriodogram::segment_variance(&single).is_none());
+        assert!(SpectralDensityPeriodogram::standard_error(&single).is_none());
+    }
 }
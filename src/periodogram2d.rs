@@ -0,0 +1,100 @@
+use crate::Signal;
+use std::ops::Deref;
+
+/// Two-dimensional periodogram
+///
+/// The periodogram of a 2D array of `(n_y,n_x)` samples, e.g. an image, is a `(m_y,m_x)` array of values in row major order,
+/// the rows being the frequencies along the first axis (`y`) and the columns the frequencies along the second axis (`x`).
+/// The periodogram is two-sided along both axes, with frequencies ordered from `-fs/2` to `fs/2`,
+/// such as the zero frequency is at the index `(m_y/2,m_x/2)`.
+///
+/// With the `serde` feature, the periodogram is serialized and deserialized together with
+/// its sampling frequencies and its shape.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Periodogram2d<T: Signal> {
+    fs: (T, T),
+    shape: (usize, usize),
+    values: Vec<T>,
+    dof: Option<T>,
+}
+impl<T: Signal> Deref for Periodogram2d<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.values.as_slice()
+    }
+}
+impl<T: Signal> Periodogram2d<T> {
+    // Creates a new [Periodogram2d] from the sampling frequencies `fs`, the `shape` and the `values` in row major order
+    pub(crate) fn new(fs: (T, T), shape: (usize, usize), values: Vec<T>) -> Self {
+        assert_eq!(
+            values.len(),
+            shape.0 * shape.1,
+            "the number of values must match the periodogram shape"
+        );
+        Self {
+            fs,
+            shape,
+            values,
+            dof: None,
+        }
+    }
+    // Sets the equivalent degrees of freedom of the periodogram
+    pub(crate) fn with_dof(self, dof: T) -> Self {
+        Self {
+            dof: Some(dof),
+            ..self
+        }
+    }
+    /// Returns the equivalent degrees of freedom of the periodogram estimate, if known
+    pub fn degrees_of_freedom(&self) -> Option<T> {
+        self.dof
+    }
+    /// Returns the number of frequencies `(m_y,m_x)` along each axis
+    pub fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+    /// Returns the sampling frequencies `(fs_y,fs_x)` along each axis
+    pub fn fs(&self) -> (T, T) {
+        self.fs
+    }
+    /// Returns the width of the frequency bins `(fs_y/m_y,fs_x/m_x)` along each axis
+    pub fn df(&self) -> (T, T) {
+        (
+            self.fs.0 / T::from_usize(self.shape.0).unwrap(),
+            self.fs.1 / T::from_usize(self.shape.1).unwrap(),
+        )
+    }
+    /// Returns the frequencies along the first axis, the frequencies of the rows
+    pub fn frequency_y(&self) -> Vec<T> {
+        frequency(self.shape.0, self.df().0)
+    }
+    /// Returns the frequencies along the second axis, the frequencies of the columns
+    pub fn frequency_x(&self) -> Vec<T> {
+        frequency(self.shape.1, self.df().1)
+    }
+    /// Returns the periodogram value at the row `i` and at the column `j`
+    pub fn value(&self, i: usize, j: usize) -> T {
+        assert!(
+            i < self.shape.0 && j < self.shape.1,
+            "the index is out of the periodogram shape"
+        );
+        self.values[i * self.shape.1 + j]
+    }
+    /// Returns the total power of the signal, the sum of the spectral density times the area `df_y df_x` of the frequency bins
+    pub fn total_power(&self) -> T {
+        let (dfy, dfx) = self.df();
+        self.values.iter().fold(T::zero(), |p, &x| p + x) * dfy * dfx
+    }
+    /// Returns the periodogram values in row major order, consuming the periodogram
+    pub fn into_inner(self) -> Vec<T> {
+        self.values
+    }
+}
+// Two-sided frequencies `i df`, with `i` within `[-m/2,m/2)`
+fn frequency<T: Signal>(m: usize, df: T) -> Vec<T> {
+    (0..m)
+        .map(|i| (T::from_usize(i).unwrap() - T::from_usize(m / 2).unwrap()) * df)
+        .collect()
+}
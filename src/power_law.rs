@@ -0,0 +1,155 @@
+use crate::{Periodogram, Signal};
+
+/// Power-law model of a spectral density
+///
+/// The model `P(f)=A f^-α + N` is the sum of a power law, e.g. a flicker noise for `α=1`,
+/// and of a white noise floor `N` (see [Periodogram::power_law_fit])
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerLaw<T> {
+    /// power law amplitude `A`, the value of the power law at 1Hz
+    pub amplitude: T,
+    /// spectral exponent `α`
+    pub exponent: T,
+    /// white noise floor `N`
+    pub floor: T,
+}
+impl<T: Signal> PowerLaw<T> {
+    /// Returns the value of the model at the frequency `f` `[Hz]`
+    pub fn value(&self, f: T) -> T {
+        self.amplitude * f.powf(-self.exponent) + self.floor
+    }
+    /// Returns the corner frequency `(A/N)^(1/α)` where the power law and the white noise floor are equal
+    pub fn corner_frequency(&self) -> T {
+        (self.amplitude / self.floor).powf(self.exponent.recip())
+    }
+}
+impl<T: Signal> Periodogram<T> {
+    /// Fits the [PowerLaw] model `P(f)=A f^-α + N` to the periodogram within the frequency band `[f_min,f_max]`
+    ///
+    /// The fit minimizes the least-squares residuals between the logarithms of the periodogram and of the model,
+    /// such as all the decades of the band have the same weight.
+    /// The exponent `α` is searched within `[-1,5]` with a grid followed by a golden-section search,
+    /// and for a given `α`, the non-negative amplitude `A` and floor `N` are derived with the Gauss-Newton algorithm.
+    /// The logarithm of a periodogram with `v` degrees of freedom is biased by `ψ(v/2)-ln(v/2)`, where `ψ` is the digamma function;
+    /// the bias is removed from `A` and `N` if the degrees of freedom are known.
+    /// The zero frequency and the null periodogram values are ignored.
+    pub fn power_law_fit(&self, f_min: T, f_max: T) -> PowerLaw<T> {
+        let (f, p): (Vec<T>, Vec<T>) = self
+            .iter()
+            .filter(|&(f, p)| f > T::zero() && f >= f_min && f <= f_max && p > T::zero())
+            .unzip();
+        assert!(
+            f.len() > 2,
+            "the power law fit requires at least 3 frequency bins within the band"
+        );
+        let fit = |alpha: T| log_fit(&f, &p, alpha);
+        let cost = |alpha: T| {
+            let model = fit(alpha);
+            f.iter()
+                .zip(&p)
+                .map(|(&f, &p)| {
+                    (p / model.value(f).max(T::min_positive_value()))
+                        .ln()
+                        .powi(2)
+                })
+                .sum::<T>()
+        };
+        let step = T::from_f64(0.1).unwrap();
+        let (mut a, mut b) = (0..=60)
+            .map(|i| T::from_f64(-1.).unwrap() + T::from_usize(i).unwrap() * step)
+            .map(|alpha| (alpha, cost(alpha)))
+            .fold(None, |best: Option<(T, T)>, x| match best {
+                Some(best) if best.1 <= x.1 => Some(best),
+                _ => Some(x),
+            })
+            .map(|(alpha, _)| (alpha - step, alpha + step))
+            .unwrap();
+        // golden-section search
+        let r = T::from_f64(0.5 * (5f64.sqrt() - 1.)).unwrap();
+        for _ in 0..40 {
+            let c = b - r * (b - a);
+            let d = a + r * (b - a);
+            if cost(c) < cost(d) {
+                b = d;
+            } else {
+                a = c;
+            }
+        }
+        let model = fit((a + b) * T::from_f64(0.5).unwrap());
+        match self.degrees_of_freedom() {
+            Some(dof) => {
+                let half_dof = dof.to_f64().unwrap() * 0.5;
+                let u = T::from_f64((half_dof.ln() - digamma(half_dof)).exp()).unwrap();
+                PowerLaw {
+                    amplitude: model.amplitude * u,
+                    floor: model.floor * u,
+                    ..model
+                }
+            }
+            None => model,
+        }
+    }
+}
+// Non-negative amplitude and floor of the power law with exponent `alpha` fitted to the logarithm of `p(f)`
+//
+// The fit starts from the minimum of the relative residuals `(p-A f^-α-N)/p`
+// followed by a few Gauss-Newton iterations on the logarithm residuals
+fn log_fit<T: Signal>(f: &[T], p: &[T], alpha: T) -> PowerLaw<T> {
+    let x: Vec<T> = f.iter().map(|&f| f.powf(-alpha)).collect();
+    let weights: Vec<T> = p.iter().map(|&p| (p * p).recip()).collect();
+    let (mut amplitude, mut floor) = linear_fit(&x, p, &weights);
+    for _ in 0..5 {
+        let model: Vec<T> = x.iter().map(|&x| amplitude * x + floor).collect();
+        if model.iter().any(|&m| m <= T::zero()) {
+            break;
+        }
+        // linearization of ln(p) - ln(m) around the model m
+        let y: Vec<T> = p
+            .iter()
+            .zip(&model)
+            .map(|(&p, &m)| m * (T::one() + (p / m).ln()))
+            .collect();
+        let weights: Vec<T> = model.iter().map(|&m| (m * m).recip()).collect();
+        (amplitude, floor) = linear_fit(&x, &y, &weights);
+    }
+    PowerLaw {
+        amplitude,
+        exponent: alpha,
+        floor,
+    }
+}
+// Non-negative `a` and `b` minimizing the weighted least-squares residuals `sum(w(y-ax-b)^2)`
+fn linear_fit<T: Signal>(x: &[T], y: &[T], w: &[T]) -> (T, T) {
+    let (sxx, sx, s1, sxy, sy) = x.iter().zip(y).zip(w).fold(
+        (T::zero(), T::zero(), T::zero(), T::zero(), T::zero()),
+        |(sxx, sx, s1, sxy, sy), ((&x, &y), &w)| {
+            (
+                sxx + w * x * x,
+                sx + w * x,
+                s1 + w,
+                sxy + w * x * y,
+                sy + w * y,
+            )
+        },
+    );
+    let det = sxx * s1 - sx * sx;
+    let a = (sxy * s1 - sx * sy) / det;
+    let b = (sxx * sy - sx * sxy) / det;
+    if det <= T::zero() || a < T::zero() {
+        (T::zero(), (sy / s1).max(T::zero()))
+    } else if b < T::zero() {
+        ((sxy / sxx).max(T::zero()), T::zero())
+    } else {
+        (a, b)
+    }
+}
+// Digamma function ψ(x) for x>0
+fn digamma(mut x: f64) -> f64 {
+    let mut psi = 0.;
+    while x < 6. {
+        psi -= 1. / x;
+        x += 1.;
+    }
+    let x2 = 1. / (x * x);
+    psi + x.ln() - 0.5 / x - x2 * (1. / 12. - x2 * (1. / 120. - x2 / 252.))
+}
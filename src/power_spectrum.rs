@@ -1,36 +1,71 @@
-use crate::{Build, Builder, One, Periodogram, PowerSpectrumPeriodogram, Signal, Welch};
+use crate::{
+    Build, Builder, One, Periodogram, PowerSpectrumPeriodogram, Sample, Signal, Welch, WelchError,
+};
 use std::{fmt::Display, ops::Deref};
 
-type WelchOne<'a, T> = Welch<'a, T, One<T>>;
+type WelchOne<'a, T, S> = Welch<'a, T, One<T>, S>;
 
 /// Power spectrum
 ///
 /// Computes a `signal` power spectrum from [Welch] [Periodogram] using [One] [Window](crate::Window)
 #[derive(Debug, Clone)]
-pub struct PowerSpectrum<'a, T: Signal>(WelchOne<'a, T>);
-impl<'a, T: Signal> PowerSpectrum<'a, T> {
+pub struct PowerSpectrum<'a, T: Signal, S: Sample<T> = T>(WelchOne<'a, T, S>);
+impl<'a, T: Signal, S: Sample<T>> PowerSpectrum<'a, T, S> {
     /// Returns [Welch] [Builder] given the `signal`
-    pub fn builder(signal: &[T]) -> Builder<T> {
+    pub fn builder(signal: &[S]) -> Builder<'_, T, One<T>, S> {
         Builder::new(signal)
     }
     /// Returns the power spectrum periodogram
     pub fn periodogram(&self) -> Periodogram<T> {
-        <WelchOne<'a, T> as PowerSpectrumPeriodogram<T>>::periodogram(&self.0)
+        <WelchOne<'a, T, S> as PowerSpectrumPeriodogram<T>>::periodogram(&self.0)
+    }
+    /// Returns the power spectrum periodogram of each of the `channels`
+    ///
+    /// The channels must have the same length than the signal the estimator is built with,
+    /// the error of the first channel rejected by [Welch::with_signal] being returned
+    pub fn channels_periodogram(
+        &self,
+        channels: &[&'a [S]],
+    ) -> Result<Vec<Periodogram<T>>, WelchError> {
+        channels
+            .iter()
+            .map(|&signal| {
+                self.0.with_signal(signal).map(|welch| {
+                    <WelchOne<'a, T, S> as PowerSpectrumPeriodogram<T>>::periodogram(&welch)
+                })
+            })
+            .collect()
+    }
+    /// Returns the power spectrum periodogram of each segment, before averaging
+    pub fn segment_periodograms(&self) -> Vec<Periodogram<T>> {
+        <WelchOne<'a, T, S> as PowerSpectrumPeriodogram<T>>::segment_periodograms(&self.0)
+    }
+    /// Returns the sample variance of the segments power spectrum, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    pub fn segment_variance(&self) -> Option<Periodogram<T>> {
+        <WelchOne<'a, T, S> as PowerSpectrumPeriodogram<T>>::segment_variance(&self.0)
+    }
+    /// Returns the standard error of the power spectrum periodogram, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    pub fn standard_error(&self) -> Option<Periodogram<T>> {
+        <WelchOne<'a, T, S> as PowerSpectrumPeriodogram<T>>::standard_error(&self.0)
     }
 }
-impl<'a, T: Signal> Build<PowerSpectrum<'a, T>> for Builder<'a, T> {
-    fn build(&self) -> PowerSpectrum<'a, T> {
+impl<'a, T: Signal, S: Sample<T>> Build<PowerSpectrum<'a, T, S>> for Builder<'a, T, One<T>, S> {
+    fn build(&self) -> PowerSpectrum<'a, T, S> {
         PowerSpectrum(self.build())
     }
 }
-impl<'a, T: Signal> Deref for PowerSpectrum<'a, T> {
-    type Target = WelchOne<'a, T>;
+impl<'a, T: Signal, S: Sample<T>> Deref for PowerSpectrum<'a, T, S> {
+    type Target = WelchOne<'a, T, S>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
-impl<'a, T: Signal> Display for PowerSpectrum<'a, T> {
+impl<'a, T: Signal, S: Sample<T>> Display for PowerSpectrum<'a, T, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
     }
@@ -0,0 +1,189 @@
+use crate::{Periodogram, Periodogram2d, Signal};
+use std::ops::Deref;
+
+/// Azimuthal average of a 2D periodogram on the radial frequency
+///
+/// The average is returned by [Periodogram2d::radial_average].
+/// The values are the mean spectral density `S(f)` of the 2D periodogram within the annuli of radius `f`,
+/// in the units of the 2D periodogram, i.e. per unit of spatial frequency squared.
+/// The power of an isotropic field is the integral of `S(f)` over the area of the annuli, `2pi f df`,
+/// and not the integral of `S(f)` over the radial frequency as for a 1D [Periodogram],
+/// so the power related methods of the radial average, like [RadialPeriodogram::total_power],
+/// weight each annulus with its area, the number of its 2D frequency bins times the area of a bin.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialPeriodogram<T: Signal> {
+    periodogram: Periodogram<T>,
+    area: Vec<T>,
+}
+impl<T: Signal> Deref for RadialPeriodogram<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.periodogram[..]
+    }
+}
+impl<T: Signal> RadialPeriodogram<T> {
+    /// Returns the azimuthal average as a one-sided [Periodogram] on the radial frequency
+    ///
+    /// The power related methods of the [Periodogram] do not apply to the azimuthal average (see [RadialPeriodogram])
+    pub fn periodogram(&self) -> &Periodogram<T> {
+        &self.periodogram
+    }
+    /// Returns the azimuthal average as a one-sided [Periodogram], consuming the radial average
+    pub fn into_periodogram(self) -> Periodogram<T> {
+        self.periodogram
+    }
+    /// Returns the radial frequencies `i df`
+    pub fn frequency(&self) -> Vec<T> {
+        self.periodogram.frequency()
+    }
+    /// Returns the width `df` of the annuli
+    pub fn df(&self) -> T {
+        self.periodogram.df()
+    }
+    /// Returns the area of each annulus, the number of its 2D frequency bins times the area `df_y df_x` of a bin
+    pub fn area(&self) -> &[T] {
+        self.area.as_slice()
+    }
+    /// Returns the power of the field within the largest radial frequency,
+    /// the sum of the values times the area of the annuli
+    ///
+    /// The power is the [Periodogram2d::total_power] of the 2D periodogram without the 2D frequency bins
+    /// beyond the largest radial frequency, i.e. in the corners of the 2D periodogram.
+    pub fn total_power(&self) -> T {
+        self.cumulative_power()
+            .last()
+            .copied()
+            .unwrap_or_else(T::zero)
+    }
+    /// Returns the variance of the field, the total power without the power of the first annulus
+    ///
+    /// See [RadialPeriodogram::total_power]; the first annulus holds the zero frequency bin, the field mean squared.
+    pub fn variance(&self) -> T {
+        self.total_power() - self.power(0)
+    }
+    /// Returns the cumulative power, the power of the field within each radial frequency
+    ///
+    /// The cumulative power at the radial frequency `f_i` is the sum of `S(f_j) A_j` for `j<=i`,
+    /// `A_j` being the area of the annulus `j` ([RadialPeriodogram::area]),
+    /// such as the last value is [RadialPeriodogram::total_power].
+    pub fn cumulative(&self) -> Periodogram<T> {
+        self.periodogram.with_values(self.cumulative_power())
+    }
+    /// Returns the cumulative root-mean-square amplitude, the square root of the cumulative power
+    ///
+    /// See [RadialPeriodogram::cumulative]
+    pub fn cumulative_rms(&self) -> Periodogram<T> {
+        self.periodogram.with_values(
+            self.cumulative_power()
+                .into_iter()
+                .map(|x| x.max(T::zero()).sqrt())
+                .collect(),
+        )
+    }
+    // Power within the annulus `i`, zero for an annulus without 2D frequency bin
+    fn power(&self, i: usize) -> T {
+        if self.area[i] > T::zero() {
+            self[i] * self.area[i]
+        } else {
+            T::zero()
+        }
+    }
+    // Running sum of the annuli power
+    fn cumulative_power(&self) -> Vec<T> {
+        (0..self.len())
+            .scan(T::zero(), |p, i| {
+                *p += self.power(i);
+                Some(*p)
+            })
+            .collect()
+    }
+}
+
+impl<T: Signal> Periodogram2d<T> {
+    /// Returns the azimuthal average of the 2D periodogram on the radial frequency `f=sqrt(f_y^2+f_x^2)`
+    ///
+    /// The radial frequencies are `i df`, the width `df` of the radial bins being the largest width of the 2D frequency bins.
+    /// Each 2D frequency bin is assigned to the annulus of its nearest radial frequency,
+    /// and the value of an annulus is the mean of the values of its 2D frequency bins,
+    /// such as the annuli are weighted by their number of bins whatever their radius and the bins aspect ratio.
+    /// The radial frequencies are limited to the smallest Nyquist frequency `min(fs_y,fs_x)/2`,
+    /// the annuli at larger radii being only partially covered by the 2D frequency bins.
+    /// The annuli without any 2D frequency bin are set to `NaN`.
+    ///
+    /// The average is in the units of the 2D periodogram, with a sampling frequency of `min(fs_y,fs_x)`;
+    /// for an isotropic spectral density `S(f)`, the total power within the radius `f` is the integral of `2pi f S(f) df`
+    /// (see [RadialPeriodogram]).
+    pub fn radial_average(&self) -> RadialPeriodogram<T> {
+        let (my, mx) = self.shape();
+        let (dfy, dfx) = self.df();
+        let (fsy, fsx) = self.fs();
+        let df = dfy.max(dfx);
+        let fs = fsy.min(fsx);
+        let n = (fs * T::from_f64(0.5).unwrap() / df)
+            .floor()
+            .to_usize()
+            .unwrap()
+            + 1;
+        let (fy, fx) = (self.frequency_y(), self.frequency_x());
+        let (sum, count) = (0..my * mx).fold(
+            (vec![T::zero(); n], vec![0usize; n]),
+            |(mut sum, mut count), k| {
+                let f = fy[k / mx].hypot(fx[k % mx]);
+                if let Some(i) = (f / df).round().to_usize().filter(|&i| i < n) {
+                    sum[i] += self[k];
+                    count[i] += 1;
+                }
+                (sum, count)
+            },
+        );
+        let values = sum
+            .into_iter()
+            .zip(&count)
+            .map(|(s, &c)| {
+                if c > 0 {
+                    s / T::from_usize(c).unwrap()
+                } else {
+                    T::nan()
+                }
+            })
+            .collect();
+        RadialPeriodogram {
+            periodogram: Periodogram::from_band(fs, T::zero(), df, values, false),
+            area: count
+                .into_iter()
+                .map(|c| T::from_usize(c).unwrap() * dfy * dfx)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Periodogram2d;
+
+    // Isotropic gaussian spectral density, negligible beyond the Nyquist frequency
+    fn gaussian() -> Periodogram2d<f64> {
+        let (my, mx) = (64, 96);
+        let p: Periodogram2d<f64> = Periodogram2d::new((1., 1.), (my, mx), vec![0.; my * mx]);
+        let (fy, fx) = (p.frequency_y(), p.frequency_x());
+        let values = (0..my * mx)
+            .map(|k| (-(fy[k / mx].powi(2) + fx[k % mx].powi(2)) / 0.01).exp())
+            .collect();
+        Periodogram2d::new((1., 1.), (my, mx), values)
+    }
+
+    #[test]
+    fn total_power() {
+        let p = gaussian();
+        let radial = p.radial_average();
+        let (pr, p2) = (radial.total_power(), p.total_power());
+        assert!((pr - p2).abs() < 1e-9 * p2, "{pr} vs {p2}");
+        // the integral of the gaussian density, pi 0.01
+        assert!((pr - std::f64::consts::PI * 0.01).abs() < 1e-3 * pr, "{pr}");
+        let cumulative = radial.cumulative();
+        assert!((cumulative[cumulative.len() - 1] - pr).abs() < 1e-12);
+        assert!(radial.variance() < pr);
+    }
+}
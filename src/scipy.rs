@@ -0,0 +1,61 @@
+use crate::{Builder, Detrend, Sample, Scaling, Signal, Window};
+
+/// [scipy.signal.welch](https://docs.scipy.org/doc/scipy/reference/generated/scipy.signal.welch.html) parameters
+///
+/// The parameters mirror the keyword arguments of `scipy.signal.welch`, with the same defaults:
+///  - `nperseg`: the segment size, `256` (or the signal length if it is shorter) if `None`,
+///  - `noverlap`: the overlap in samples, `nperseg/2` if `None`,
+///  - `nfft`: the size of the discrete Fourier transform, `nperseg` if `None`,
+///  - `detrend`: the segments [Detrend], [Detrend::Constant] if `None`,
+///  - `return_onesided`: returns the one-sided spectrum of real signals, `true` if `None`,
+///  - `scaling`: the periodogram [Scaling] of [Welch::periodogram](crate::Welch::periodogram), [Scaling::Density] if `None`.
+///
+/// Unlike `scipy.signal.welch`, the two-sided spectrum is ordered from the negative to the positive frequencies.
+///
+/// The [Builder] returned by [Scipy::builder] is set accordingly, e.g.
+///```
+///use welch_sde::{Build, Scipy, SpectralDensity};
+///
+///let signal = vec![0f64; 10_000];
+///let sd: SpectralDensity<f64> = Scipy {
+///    nperseg: Some(1024),
+///    ..Default::default()
+///}
+///.builder(&signal, 1e3)
+///.build();
+///```
+#[derive(Debug, Clone, Default)]
+pub struct Scipy<T: Signal = f64> {
+    /// length of each segment
+    pub nperseg: Option<usize>,
+    /// number of samples to overlap between segments
+    pub noverlap: Option<usize>,
+    /// length of the discrete Fourier transform
+    pub nfft: Option<usize>,
+    /// segments detrending
+    pub detrend: Option<Detrend<T>>,
+    /// one-sided spectrum of real signals
+    pub return_onesided: Option<bool>,
+    /// periodogram scaling
+    pub scaling: Option<Scaling>,
+}
+impl<T: Signal> Scipy<T> {
+    /// Returns a [Builder] for the `signal` sampled at `fs`Hz set with the `scipy.signal.welch` parameters
+    pub fn builder<'a, W: Window<T>, S: Sample<T>>(
+        &self,
+        signal: &'a [S],
+        fs: T,
+    ) -> Builder<'a, T, W, S> {
+        let nperseg = self.nperseg.unwrap_or(256).min(signal.len());
+        Builder::new(signal)
+            .sampling_frequency(fs)
+            .unbounded_dft_size()
+            .power_of_two_dft(false)
+            .segment_size(nperseg)
+            .overlap_size(self.noverlap.unwrap_or(nperseg / 2))
+            .dft_size(self.nfft.unwrap_or(nperseg))
+            .detrend(self.detrend.clone().unwrap_or(Detrend::Constant))
+            .two_sided(!self.return_onesided.unwrap_or(true))
+            .scaling(self.scaling.unwrap_or_default())
+    }
+}
@@ -0,0 +1,196 @@
+use num_complex::Complex;
+use num_traits::Float;
+
+// Vectorized kernels of the segments windowing and of the periodograms accumulation
+//
+// The kernels are implemented for any floating point type with the default scalar loops and,
+// with the `simd` feature, with the SIMD types of the `wide` crate for `f32` and `f64`
+pub(crate) trait Vectorized: Float + std::ops::AddAssign + 'static {
+    // Writes the product of the samples `x` with the window `w` into `y`
+    fn windowed(x: &[Self], w: &[Self], y: &mut [Self]) {
+        #[cfg(feature = "simd")]
+        if lanes::windowed(x, w, y) {
+            return;
+        }
+        y.iter_mut()
+            .zip(x.iter().zip(w))
+            .for_each(|(y, (&x, &w))| *y = x * w);
+    }
+    // Adds `x` to `y`
+    fn accumulate(y: &mut [Self], x: &[Self]) {
+        #[cfg(feature = "simd")]
+        if lanes::accumulate(y, x) {
+            return;
+        }
+        y.iter_mut().zip(x).for_each(|(y, &x)| *y += x);
+    }
+    // Returns the squared magnitude of the complex numbers `z`
+    fn norm_sqr(z: &[Complex<Self>]) -> Vec<Self> {
+        #[cfg(feature = "simd")]
+        if let Some(p) = lanes::norm_sqr(z) {
+            return p;
+        }
+        z.iter().map(|z| z.norm_sqr()).collect()
+    }
+}
+impl<T: Float + std::ops::AddAssign + 'static> Vectorized for T {}
+
+#[cfg(feature = "simd")]
+mod lanes {
+    use num_complex::Complex;
+    use std::{any::TypeId, mem::ManuallyDrop};
+    use wide::{f32x8, f64x4};
+
+    // SIMD kernels of the `f32` and `f64` types
+    trait Lanes: Sized + 'static {
+        fn windowed(x: &[Self], w: &[Self], y: &mut [Self]);
+        fn accumulate(y: &mut [Self], x: &[Self]);
+        fn norm_sqr(z: &[Complex<Self>]) -> Vec<Self>;
+    }
+
+    // Reinterprets the slice of `T` as a slice of `U` if both are the same type
+    fn cast<T: 'static, U: 'static>(x: &[T]) -> Option<&[U]> {
+        (TypeId::of::<T>() == TypeId::of::<U>())
+            .then(|| unsafe { std::slice::from_raw_parts(x.as_ptr() as *const U, x.len()) })
+    }
+    fn cast_mut<T: 'static, U: 'static>(x: &mut [T]) -> Option<&mut [U]> {
+        (TypeId::of::<T>() == TypeId::of::<U>())
+            .then(|| unsafe { std::slice::from_raw_parts_mut(x.as_mut_ptr() as *mut U, x.len()) })
+    }
+    fn cast_vec<T: 'static, U: 'static>(x: Vec<T>) -> Option<Vec<U>> {
+        (TypeId::of::<T>() == TypeId::of::<U>()).then(|| {
+            let mut x = ManuallyDrop::new(x);
+            unsafe { Vec::from_raw_parts(x.as_mut_ptr() as *mut U, x.len(), x.capacity()) }
+        })
+    }
+
+    // Calls the SIMD kernels if `T` is either `f32` or `f64`, returning `false` otherwise
+    pub(super) fn windowed<T: 'static>(x: &[T], w: &[T], y: &mut [T]) -> bool {
+        fn with<T: 'static, L: Lanes>(x: &[T], w: &[T], y: &mut [T]) -> bool {
+            match (cast(x), cast(w), cast_mut(y)) {
+                (Some(x), Some(w), Some(y)) => {
+                    L::windowed(x, w, y);
+                    true
+                }
+                _ => false,
+            }
+        }
+        with::<T, f32>(x, w, y) || with::<T, f64>(x, w, y)
+    }
+    pub(super) fn accumulate<T: 'static>(y: &mut [T], x: &[T]) -> bool {
+        fn with<T: 'static, L: Lanes>(y: &mut [T], x: &[T]) -> bool {
+            match (cast_mut(y), cast(x)) {
+                (Some(y), Some(x)) => {
+                    L::accumulate(y, x);
+                    true
+                }
+                _ => false,
+            }
+        }
+        with::<T, f32>(y, x) || with::<T, f64>(y, x)
+    }
+    pub(super) fn norm_sqr<T: 'static>(z: &[Complex<T>]) -> Option<Vec<T>> {
+        fn with<T: 'static, L: Lanes>(z: &[Complex<T>]) -> Option<Vec<T>> {
+            cast::<_, Complex<L>>(z).map(L::norm_sqr).and_then(cast_vec)
+        }
+        with::<T, f32>(z).or_else(|| with::<T, f64>(z))
+    }
+
+    // Reinterprets the complex numbers as interleaved real and imaginary parts
+    fn interleaved<T>(z: &[Complex<T>]) -> &[T] {
+        // `Complex<T>` is `#[repr(C)]` with the real part followed by the imaginary part
+        unsafe { std::slice::from_raw_parts(z.as_ptr() as *const T, 2 * z.len()) }
+    }
+
+    impl Lanes for f32 {
+        fn windowed(x: &[Self], w: &[Self], y: &mut [Self]) {
+            let n = x.len().min(w.len()).min(y.len());
+            let (x, w, y) = (&x[..n], &w[..n], &mut y[..n]);
+            let (mut xc, mut wc, mut yc) =
+                (x.chunks_exact(8), w.chunks_exact(8), y.chunks_exact_mut(8));
+            (&mut xc).zip(&mut wc).zip(&mut yc).for_each(|((x, w), y)| {
+                let v = f32x8::new(x.try_into().unwrap()) * f32x8::new(w.try_into().unwrap());
+                y.copy_from_slice(v.as_array_ref());
+            });
+            yc.into_remainder()
+                .iter_mut()
+                .zip(xc.remainder().iter().zip(wc.remainder()))
+                .for_each(|(y, (&x, &w))| *y = x * w);
+        }
+        fn accumulate(y: &mut [Self], x: &[Self]) {
+            let n = x.len().min(y.len());
+            let (x, y) = (&x[..n], &mut y[..n]);
+            let (mut xc, mut yc) = (x.chunks_exact(8), y.chunks_exact_mut(8));
+            (&mut xc).zip(&mut yc).for_each(|(x, y)| {
+                let v = f32x8::new((&*y).try_into().unwrap()) + f32x8::new(x.try_into().unwrap());
+                y.copy_from_slice(v.as_array_ref());
+            });
+            yc.into_remainder()
+                .iter_mut()
+                .zip(xc.remainder())
+                .for_each(|(y, &x)| *y += x);
+        }
+        fn norm_sqr(z: &[Complex<Self>]) -> Vec<Self> {
+            let x = interleaved(z);
+            let mut p = Vec::with_capacity(z.len());
+            let mut xc = x.chunks_exact(8);
+            (&mut xc).for_each(|x| {
+                let v = f32x8::new(x.try_into().unwrap());
+                let s = (v * v).to_array();
+                p.extend_from_slice(&[s[0] + s[1], s[2] + s[3], s[4] + s[5], s[6] + s[7]]);
+            });
+            p.extend(
+                xc.remainder()
+                    .chunks_exact(2)
+                    .map(|x| x[0] * x[0] + x[1] * x[1]),
+            );
+            p
+        }
+    }
+
+    impl Lanes for f64 {
+        fn windowed(x: &[Self], w: &[Self], y: &mut [Self]) {
+            let n = x.len().min(w.len()).min(y.len());
+            let (x, w, y) = (&x[..n], &w[..n], &mut y[..n]);
+            let (mut xc, mut wc, mut yc) =
+                (x.chunks_exact(4), w.chunks_exact(4), y.chunks_exact_mut(4));
+            (&mut xc).zip(&mut wc).zip(&mut yc).for_each(|((x, w), y)| {
+                let v = f64x4::new(x.try_into().unwrap()) * f64x4::new(w.try_into().unwrap());
+                y.copy_from_slice(v.as_array_ref());
+            });
+            yc.into_remainder()
+                .iter_mut()
+                .zip(xc.remainder().iter().zip(wc.remainder()))
+                .for_each(|(y, (&x, &w))| *y = x * w);
+        }
+        fn accumulate(y: &mut [Self], x: &[Self]) {
+            let n = x.len().min(y.len());
+            let (x, y) = (&x[..n], &mut y[..n]);
+            let (mut xc, mut yc) = (x.chunks_exact(4), y.chunks_exact_mut(4));
+            (&mut xc).zip(&mut yc).for_each(|(x, y)| {
+                let v = f64x4::new((&*y).try_into().unwrap()) + f64x4::new(x.try_into().unwrap());
+                y.copy_from_slice(v.as_array_ref());
+            });
+            yc.into_remainder()
+                .iter_mut()
+                .zip(xc.remainder())
+                .for_each(|(y, &x)| *y += x);
+        }
+        fn norm_sqr(z: &[Complex<Self>]) -> Vec<Self> {
+            let x = interleaved(z);
+            let mut p = Vec::with_capacity(z.len());
+            let mut xc = x.chunks_exact(4);
+            (&mut xc).for_each(|x| {
+                let v = f64x4::new(x.try_into().unwrap());
+                let s = (v * v).to_array();
+                p.extend_from_slice(&[s[0] + s[1], s[2] + s[3]]);
+            });
+            p.extend(
+                xc.remainder()
+                    .chunks_exact(2)
+                    .map(|x| x[0] * x[0] + x[1] * x[1]),
+            );
+            p
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{Periodogram, Signal};
+
+impl<T: Signal> Periodogram<T> {
+    /// Returns the periodogram smoothed with the Daniell kernel, a moving average over `width` bins
+    ///
+    /// `width` must be odd (see [Periodogram::smoothed_with])
+    pub fn smoothed(&self, width: usize) -> Periodogram<T> {
+        self.smoothed_with(&vec![T::one(); width])
+    }
+    /// Returns the periodogram convolved with the `kernel`
+    ///
+    /// The kernel is normalized to a unit sum and its length must be odd, the kernel being centered on each bin.
+    /// The periodogram is extended beyond its edges according to its symmetry: one-sided periodograms are mirrored
+    /// around the zero and the last frequencies, whereas two-sided periodograms are periodic.
+    /// The degrees of freedom of the estimate are multiplied by `1/sum(k^2)`, where `k` is the normalized kernel,
+    /// assuming independent frequency bins.
+    pub fn smoothed_with(&self, kernel: &[T]) -> Periodogram<T> {
+        assert!(
+            kernel.len() % 2 == 1,
+            "the smoothing kernel length must be odd"
+        );
+        let sum = kernel.iter().cloned().sum::<T>();
+        let kernel: Vec<T> = kernel.iter().map(|&k| k / sum).collect();
+        let n = self.len() as isize;
+        let h = (kernel.len() / 2) as isize;
+        let index = |i: isize| -> usize {
+            if self.is_two_sided() {
+                i.rem_euclid(n) as usize
+            } else if n == 1 {
+                0
+            } else {
+                // reflection around the first and last bins
+                let i = i.rem_euclid(2 * (n - 1));
+                (if i < n { i } else { 2 * (n - 1) - i }) as usize
+            }
+        };
+        let values = (0..n)
+            .map(|i| {
+                kernel
+                    .iter()
+                    .zip(i - h..)
+                    .map(|(&k, j)| k * self[index(j)])
+                    .sum::<T>()
+            })
+            .collect();
+        let periodogram = self.with_values(values);
+        match self.degrees_of_freedom() {
+            Some(dof) => periodogram.with_dof(dof / kernel.iter().map(|&k| k * k).sum::<T>()),
+            None => periodogram,
+        }
+    }
+}
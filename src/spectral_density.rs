@@ -1,36 +1,72 @@
-use crate::{Build, Builder, Hann, Periodogram, Signal, SpectralDensityPeriodogram, Welch};
+use crate::{
+    Build, Builder, Hann, Periodogram, Sample, Signal, SpectralDensityPeriodogram, Welch,
+    WelchError,
+};
 use std::{fmt::Display, ops::Deref};
 
-type WelchHann<'a, T> = Welch<'a, T, Hann<T>>;
+type WelchHann<'a, T, S> = Welch<'a, T, Hann<T>, S>;
 
 /// Spectral density
 ///
 /// Computes a `signal` spectral density from [Welch] [Periodogram] using [Hann] [Window](crate::Window)
 #[derive(Debug, Clone)]
-pub struct SpectralDensity<'a, T: Signal>(WelchHann<'a, T>);
-impl<'a, T: Signal> SpectralDensity<'a, T> {
+pub struct SpectralDensity<'a, T: Signal, S: Sample<T> = T>(WelchHann<'a, T, S>);
+impl<'a, T: Signal, S: Sample<T>> SpectralDensity<'a, T, S> {
     /// Returns [Welch] [Builder] given the `signal` sampled at `fs`Hz
-    pub fn builder(signal: &[T], fs: T) -> Builder<T> {
+    pub fn builder(signal: &[S], fs: T) -> Builder<'_, T, Hann<T>, S> {
         Builder::new(signal).sampling_frequency(fs)
     }
     /// Returns the spectral density periodogram
     pub fn periodogram(&self) -> Periodogram<T> {
-        <WelchHann<'a, T> as SpectralDensityPeriodogram<T>>::periodogram(&self.0)
+        <WelchHann<'a, T, S> as SpectralDensityPeriodogram<T>>::periodogram(&self.0)
+    }
+    /// Returns the spectral density periodogram of each of the `channels`
+    ///
+    /// The channels must have the same length than the signal the estimator is built with,
+    /// the error of the first channel rejected by [Welch::with_signal] being returned
+    pub fn channels_periodogram(
+        &self,
+        channels: &[&'a [S]],
+    ) -> Result<Vec<Periodogram<T>>, WelchError> {
+        channels
+            .iter()
+            .map(|&signal| {
+                self.0.with_signal(signal).map(|welch| {
+                    <WelchHann<'a, T, S> as SpectralDensityPeriodogram<T>>::periodogram(&welch)
+                })
+            })
+            .collect()
+    }
+    /// Returns the spectral density periodogram of each segment, before averaging
+    pub fn segment_periodograms(&self) -> Vec<Periodogram<T>> {
+        <WelchHann<'a, T, S> as SpectralDensityPeriodogram<T>>::segment_periodograms(&self.0)
+    }
+    /// Returns the sample variance of the segments spectral density, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    pub fn segment_variance(&self) -> Option<Periodogram<T>> {
+        <WelchHann<'a, T, S> as SpectralDensityPeriodogram<T>>::segment_variance(&self.0)
+    }
+    /// Returns the standard error of the spectral density periodogram, per frequency bin
+    ///
+    /// Returns `None` if there are less than 2 segments
+    pub fn standard_error(&self) -> Option<Periodogram<T>> {
+        <WelchHann<'a, T, S> as SpectralDensityPeriodogram<T>>::standard_error(&self.0)
     }
 }
-impl<'a, T: Signal> Build<SpectralDensity<'a, T>> for Builder<'a, T> {
-    fn build(&self) -> SpectralDensity<'a, T> {
+impl<'a, T: Signal, S: Sample<T>> Build<SpectralDensity<'a, T, S>> for Builder<'a, T, Hann<T>, S> {
+    fn build(&self) -> SpectralDensity<'a, T, S> {
         SpectralDensity(self.build())
     }
 }
-impl<'a, T: Signal> Deref for SpectralDensity<'a, T> {
-    type Target = WelchHann<'a, T>;
+impl<'a, T: Signal, S: Sample<T>> Deref for SpectralDensity<'a, T, S> {
+    type Target = WelchHann<'a, T, S>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
-impl<'a, T: Signal> Display for SpectralDensity<'a, T> {
+impl<'a, T: Signal, S: Sample<T>> Display for SpectralDensity<'a, T, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.0.fmt(f)
     }
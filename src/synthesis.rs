@@ -0,0 +1,215 @@
+use crate::{Interpolation, Periodogram, Signal};
+use num_complex::Complex;
+use realfft::RealFftPlanner;
+
+/// Synthesis method of a signal with a prescribed spectral density
+///
+/// The signal is the inverse discrete Fourier transform of random Fourier coefficients
+/// which expected squared magnitude is given by the spectral density at the frequencies `k fs/n`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Synthesis {
+    /// filtered white noise: the real and imaginary parts of the Fourier coefficients are normally distributed,
+    /// the signal being a realization of a Gaussian process which periodogram fluctuates around the spectral density
+    #[default]
+    Gaussian,
+    /// random phase: the magnitude of the Fourier coefficients is set by the spectral density
+    /// and their phase is uniformly distributed, the periodogram of the whole signal matching the spectral density exactly
+    RandomPhase,
+}
+
+// SplitMix64 pseudo-random number generator
+struct SplitMix64(u64);
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    // Uniform random number within (0,1]
+    fn uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 1.) / (1u64 << 53) as f64
+    }
+    // Pair of independent standard normal random numbers (Box-Muller transform)
+    fn normal_pair(&mut self) -> (f64, f64) {
+        let r = (-2. * self.uniform().ln()).sqrt();
+        let (sin, cos) = (2. * std::f64::consts::PI * self.uniform()).sin_cos();
+        (r * cos, r * sin)
+    }
+}
+
+// Synthesizes `n` samples at `fs`Hz from the one-sided spectral `density` at the frequencies `k fs/n`, `k` within `[0,n/2]`
+fn synthesize_bins<T: Signal>(
+    n: usize,
+    fs: T,
+    density: &[T],
+    method: Synthesis,
+    seed: u64,
+) -> Vec<T> {
+    let mut rng = SplitMix64(seed);
+    let nfs = fs.to_f64().unwrap() * n as f64;
+    let spectrum: Vec<Complex<T>> = density
+        .iter()
+        .enumerate()
+        .map(|(k, &s)| {
+            let s = s.to_f64().unwrap().max(0.);
+            // the zero and Nyquist frequencies are not folded in the one-sided density and their coefficients are real
+            let real = k == 0 || 2 * k == n;
+            let (re, im) = match (method, real) {
+                (Synthesis::Gaussian, true) => ((nfs * s).sqrt() * rng.normal_pair().0, 0.),
+                (Synthesis::Gaussian, false) => {
+                    let (a, b) = rng.normal_pair();
+                    let u = (0.25 * nfs * s).sqrt();
+                    (u * a, u * b)
+                }
+                (Synthesis::RandomPhase, true) => {
+                    let sign = if rng.next_u64() >> 63 == 0 { 1. } else { -1. };
+                    (sign * (nfs * s).sqrt(), 0.)
+                }
+                (Synthesis::RandomPhase, false) => {
+                    let (sin, cos) = (2. * std::f64::consts::PI * rng.uniform()).sin_cos();
+                    let u = (0.5 * nfs * s).sqrt();
+                    (u * cos, u * sin)
+                }
+            };
+            Complex::new(T::from_f64(re).unwrap(), T::from_f64(im).unwrap())
+        })
+        .collect();
+    let ifft = RealFftPlanner::new().plan_fft_inverse(n);
+    let mut input = ifft.make_input_vec();
+    input.copy_from_slice(&spectrum);
+    let mut output = ifft.make_output_vec();
+    ifft.process(&mut input, &mut output)
+        .expect("complex-to-real Fourier transform failed");
+    let u = T::from_usize(n).unwrap().recip();
+    output.into_iter().map(|x| x * u).collect()
+}
+
+/// Returns `n` samples at `fs`Hz of a real signal with the one-sided spectral `density` (signal unit squared per Hertz)
+///
+/// The spectral density follows the convention of the one-sided periodograms of this crate:
+/// the density at the positive frequencies accounts for the negative frequencies,
+/// such as the integral of the density from the zero to the Nyquist frequency is the signal variance.
+/// The signal is synthesized according to the [Synthesis] method, the random numbers being generated
+/// from the `seed` such as the same `seed` gives the same signal.
+/// Negative or NaN values of the density are set to zero.
+pub fn synthesize<T: Signal, F: Fn(T) -> T>(
+    n: usize,
+    fs: T,
+    density: F,
+    method: Synthesis,
+    seed: u64,
+) -> Vec<T> {
+    let df = fs / T::from_usize(n).unwrap();
+    let density: Vec<T> = (0..=n / 2)
+        .map(|k| density(T::from_usize(k).unwrap() * df))
+        .collect();
+    synthesize_bins(n, fs, &density, method, seed)
+}
+
+impl<T: Signal> Periodogram<T> {
+    /// Returns `n` samples of a real signal, sampled at [Periodogram::fs], which spectral density is the periodogram
+    ///
+    /// The periodogram is a spectral density ([Scaling::Density](crate::Scaling::Density)), and it is linearly interpolated
+    /// at the frequencies `k fs/n` (see [Periodogram::interpolate]), the frequencies beyond the range of the periodogram
+    /// being given the value of the nearest bin.
+    /// The one-sided density of a two-sided periodogram at the frequency `f` is the sum of the periodogram at `f` and `-f`.
+    /// The signal is synthesized with [synthesize].
+    pub fn synthesize(&self, n: usize, method: Synthesis, seed: u64) -> Vec<T> {
+        let fs = self.fs();
+        let df = fs / T::from_usize(n).unwrap();
+        let f_last = self.f0() + T::from_usize(self.len() - 1).unwrap() * self.df();
+        let values = |sign: T| {
+            let frequency: Vec<T> = (0..=n / 2)
+                .map(|k| {
+                    (sign * T::from_usize(k).unwrap() * df)
+                        .max(self.f0())
+                        .min(f_last)
+                })
+                .collect();
+            self.interpolate(&frequency, Interpolation::Linear)
+        };
+        let mut density = values(T::one());
+        if self.is_two_sided() {
+            density
+                .iter_mut()
+                .zip(values(-T::one()))
+                .enumerate()
+                .filter(|(k, _)| *k > 0 && 2 * k != n)
+                .for_each(|(_, (s, s_neg))| *s += s_neg);
+        }
+        synthesize_bins(n, fs, &density, method, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Build, Builder, Hann, One, Welch};
+
+    fn welch(x: &[f64], fs: f64) -> Welch<'_, f64, Hann<f64>> {
+        Builder::new(x)
+            .sampling_frequency(fs)
+            .segment_size(256)
+            .build()
+    }
+    // One-sided spectral density decreasing by a factor 26 from the zero to the Nyquist frequency at 10Hz
+    fn density(f: f64) -> f64 {
+        2. / (1. + f * f)
+    }
+
+    #[test]
+    fn gaussian_round_trip() {
+        let fs = 10.;
+        let x = synthesize(1 << 18, fs, density, Synthesis::Gaussian, 109);
+        let welch: Welch<f64, Hann<f64>> = Builder::new(&x)
+            .sampling_frequency(fs)
+            .segment_size(1024)
+            .build();
+        // the zero and Nyquist frequency bins of the segments are not folded and hold half the density
+        let periodogram = welch.periodogram();
+        let n = periodogram.len();
+        periodogram.iter().skip(1).take(n - 2).for_each(|(f, p)| {
+            let s = density(f);
+            assert!((p / s - 1.).abs() < 0.2, "{f}Hz: {p} vs {s}");
+        });
+        // the variance is the integral of the spectral density, 2atan(5)
+        let var = x.iter().map(|x| x * x).sum::<f64>() / x.len() as f64;
+        let expected = 2. * 5f64.atan();
+        assert!((var / expected - 1.).abs() < 2e-2, "{var} vs {expected}");
+    }
+
+    #[test]
+    fn random_phase_periodogram() {
+        // the periodogram of the whole signal is the spectral density at the frequencies k fs/n
+        let (n, fs) = (4096, 10.);
+        let x = synthesize(n, fs, density, Synthesis::RandomPhase, 109);
+        let welch: Welch<f64, One<f64>> = Builder::new(&x)
+            .sampling_frequency(fs)
+            .segment_size(n)
+            .build();
+        welch.periodogram().iter().for_each(|(f, p)| {
+            let s = density(f);
+            assert!((p / s - 1.).abs() < 1e-10, "{f}Hz: {p} vs {s}");
+        });
+    }
+
+    #[test]
+    fn periodogram_round_trip() {
+        // the signal synthesized from a periodogram has the same periodogram
+        let fs = 10.;
+        let x = synthesize(1 << 16, fs, density, Synthesis::Gaussian, 109);
+        let periodogram = welch(&x, fs).periodogram();
+        let y = periodogram.synthesize(1 << 16, Synthesis::RandomPhase, 110);
+        welch(&y, fs)
+            .periodogram()
+            .iter()
+            .zip(periodogram.iter())
+            .skip(1)
+            .take(periodogram.len() - 2)
+            .for_each(|((f, q), (_, p))| {
+                assert!((q / p - 1.).abs() < 0.2, "{f}Hz: {q} vs {p}");
+            });
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{Build, Builder, CrossSpectralDensity, Hann, Periodogram, Signal, Window};
+use num_complex::Complex;
+use std::{fmt::Display, ops::Deref};
+
+/// Transfer function
+///
+/// Estimates the frequency response of a system from its input `x` and output `y` signals,
+/// using the auto spectral densities `Pxx` and `Pyy` and the cross spectral density `Pxy`
+/// from [CrossSpectralDensity]:
+///  - `H1 = Pxy/Pxx`, optimal for noise on the output,
+///  - `H2 = Pyy/Pyx`, optimal for noise on the input,
+///  - `Hv`, the total least-squares estimate for noise on both input and output.
+#[derive(Debug, Clone)]
+pub struct TransferFunction<'a, T: Signal, W: Window<T> = Hann<T>>(
+    pub(crate) CrossSpectralDensity<'a, T, W>,
+);
+impl<'a, T: Signal, W: Window<T>> TransferFunction<'a, T, W> {
+    /// Returns [Welch](crate::Welch) [Builder] given the input `x` and output `y` signals sampled at `fs`Hz
+    pub fn builder(x: &'a [T], y: &'a [T], fs: T) -> Builder<'a, T, W> {
+        CrossSpectralDensity::builder(x, y, fs)
+    }
+    /// Returns the `H1=Pxy/Pxx` transfer function estimate
+    pub fn h1(&self) -> Periodogram<T, Complex<T>> {
+        let (pxx, _, pxy) = self.spectra();
+        self.x().to_periodogram(
+            pxy.into_iter()
+                .zip(pxx)
+                .map(|(pxy, pxx)| pxy / pxx)
+                .collect(),
+        )
+    }
+    /// Returns the `H2=Pyy/Pyx` transfer function estimate
+    pub fn h2(&self) -> Periodogram<T, Complex<T>> {
+        let (_, pyy, pxy) = self.spectra();
+        self.x().to_periodogram(
+            pxy.into_iter()
+                .zip(pyy)
+                .map(|(pxy, pyy)| pxy.conj().inv() * pyy)
+                .collect(),
+        )
+    }
+    /// Returns the `Hv` transfer function estimate
+    ///
+    /// `Hv=(Pyy-Pxx+sqrt((Pxx-Pyy)^2+4|Pxy|^2))/(2Pyx)`
+    pub fn hv(&self) -> Periodogram<T, Complex<T>> {
+        let (pxx, pyy, pxy) = self.spectra();
+        let two = T::from_f64(2.).unwrap();
+        let four = T::from_f64(4.).unwrap();
+        self.x().to_periodogram(
+            pxx.into_iter()
+                .zip(pyy)
+                .zip(pxy)
+                .map(|((pxx, pyy), pxy)| {
+                    let a = pyy - pxx + ((pxx - pyy).powi(2) + four * pxy.norm_sqr()).sqrt();
+                    (pxy.conj() * two).inv() * a
+                })
+                .collect(),
+        )
+    }
+    /// Returns the magnitude squared coherence `|Pxy|^2/(Pxx Pyy)`
+    pub fn coherence(&self) -> Periodogram<T> {
+        let (pxx, pyy, pxy) = self.spectra();
+        self.x().to_periodogram(
+            pxx.into_iter()
+                .zip(pyy)
+                .zip(pxy)
+                .map(|((pxx, pyy), pxy)| pxy.norm_sqr() / (pxx * pyy))
+                .collect(),
+        )
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Build<TransferFunction<'a, T, W>> for Builder<'a, T, W> {
+    fn build(&self) -> TransferFunction<'a, T, W> {
+        TransferFunction(self.build())
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Deref for TransferFunction<'a, T, W> {
+    type Target = CrossSpectralDensity<'a, T, W>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl<'a, T: Signal, W: Window<T>> Display for TransferFunction<'a, T, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+
+    // White noise input `x` and output `y` of the filter `y(n)=x(n)+x(n-1)/2` with white noise of standard deviation `sigma_n`
+    fn signals(n: usize, sigma_n: f64) -> (Vec<f64>, Vec<f64>) {
+        let mut rng = StdRng::seed_from_u64(23);
+        let x: Vec<f64> = (0..n).map(|_| StandardNormal.sample(&mut rng)).collect();
+        let y: Vec<f64> = (0..n)
+            .map(|i| {
+                let e: f64 = StandardNormal.sample(&mut rng);
+                x[i] + if i > 0 { 0.5 * x[i - 1] } else { 0. } + sigma_n * e
+            })
+            .collect();
+        (x, y)
+    }
+    // Frequency response of the filter
+    fn response(f: f64, fs: f64) -> Complex<f64> {
+        Complex::new(1., 0.) + Complex::from_polar(0.5, -2. * std::f64::consts::PI * f / fs)
+    }
+
+    #[test]
+    fn noise_free() {
+        let fs = 10.;
+        let (x, y) = signals(100_000, 0.);
+        let tf: TransferFunction<f64> = TransferFunction::builder(&x, &y, fs).build();
+        let coherence = tf.coherence();
+        for h in [tf.h1(), tf.h2(), tf.hv()] {
+            h.iter().zip(coherence.iter()).for_each(|((f, h), (_, c))| {
+                let expected = response(f, fs);
+                assert!((h - expected).norm() < 1e-2, "{f}Hz: {h} vs {expected}");
+                assert!(c > 0.99, "{f}Hz: {c}");
+            });
+        }
+    }
+
+    #[test]
+    fn output_noise() {
+        // H1 is unbiased and the coherence is |H|^2/(|H|^2+sigma_n^2)
+        let (fs, sigma_n) = (10., 0.25);
+        let (x, y) = signals(100_000, sigma_n);
+        let tf: TransferFunction<f64> = TransferFunction::builder(&x, &y, fs)
+            .segment_size(256)
+            .build();
+        let coherence = tf.coherence();
+        tf.h1()
+            .iter()
+            .zip(coherence.iter())
+            .for_each(|((f, h), (_, c))| {
+                let expected = response(f, fs);
+                assert!(
+                    (h - expected).norm() < 5e-2 * expected.norm(),
+                    "{f}Hz: {h} vs {expected}"
+                );
+                let g = expected.norm_sqr();
+                let expected = g / (g + sigma_n * sigma_n);
+                assert!((c - expected).abs() < 5e-2, "{f}Hz: {c} vs {expected}");
+            });
+    }
+}
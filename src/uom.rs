@@ -0,0 +1,250 @@
+use crate::{Build, Builder, Hann, Periodogram, Scaling, Signal, Welch, WelchError, Window};
+use ::uom::{
+    si::{frequency::Frequency, Dimension, Quantity, Units, ISQ, SI},
+    typenum::{Integer, Sum, P1},
+    Conversion, Kind,
+};
+use std::{fmt, marker::PhantomData, ops::Add};
+
+/// Dimensions of the power spectrum and of the spectral density of a signal of dimension `Self`
+///
+/// The power spectrum of a signal of unit `u` is in `u²` and its spectral density is in `u²/Hz`, i.e. `u²s`
+pub trait SpectralDimension {
+    /// dimension of the power spectrum
+    type Spectrum: Dimension + ?Sized;
+    /// dimension of the spectral density
+    type Density: Dimension + ?Sized;
+}
+impl<L, M, T, I, Th, N, J, K> SpectralDimension for ISQ<L, M, T, I, Th, N, J, K>
+where
+    L: Integer + Add<L>,
+    M: Integer + Add<M>,
+    T: Integer + Add<T>,
+    I: Integer + Add<I>,
+    Th: Integer + Add<Th>,
+    N: Integer + Add<N>,
+    J: Integer + Add<J>,
+    K: ?Sized,
+    Sum<L, L>: Integer,
+    Sum<M, M>: Integer,
+    Sum<T, T>: Integer + Add<P1>,
+    Sum<Sum<T, T>, P1>: Integer,
+    Sum<I, I>: Integer,
+    Sum<Th, Th>: Integer,
+    Sum<N, N>: Integer,
+    Sum<J, J>: Integer,
+{
+    type Spectrum = ISQ<
+        Sum<L, L>,
+        Sum<M, M>,
+        Sum<T, T>,
+        Sum<I, I>,
+        Sum<Th, Th>,
+        Sum<N, N>,
+        Sum<J, J>,
+        dyn Kind,
+    >;
+    type Density = ISQ<
+        Sum<L, L>,
+        Sum<M, M>,
+        Sum<Sum<T, T>, P1>,
+        Sum<I, I>,
+        Sum<Th, Th>,
+        Sum<N, N>,
+        Sum<J, J>,
+        dyn Kind,
+    >;
+}
+
+// Quantity of dimension `D` with the `value` in the SI base units
+fn quantity<T, D>(value: T) -> Quantity<D, SI<T>, T>
+where
+    T: Signal + Conversion<T>,
+    SI<T>: Units<T>,
+    D: Dimension + ?Sized,
+{
+    Quantity {
+        dimension: PhantomData,
+        units: PhantomData,
+        value,
+    }
+}
+
+/// Periodogram with values of dimension `D`
+///
+/// The values are stored in the SI base units of the dimension.
+pub struct QuantityPeriodogram<T: Signal, D: ?Sized> {
+    periodogram: Periodogram<T>,
+    dimension: PhantomData<D>,
+}
+impl<T, D> QuantityPeriodogram<T, D>
+where
+    T: Signal + Conversion<T>,
+    SI<T>: Units<T>,
+    D: Dimension + ?Sized,
+{
+    /// Returns the periodogram values in the SI base units
+    pub fn periodogram(&self) -> &Periodogram<T> {
+        &self.periodogram
+    }
+    /// Returns the periodogram values in the SI base units, consuming the periodogram
+    pub fn into_periodogram(self) -> Periodogram<T> {
+        self.periodogram
+    }
+    /// Returns the frequency vector (see [Periodogram::frequency])
+    pub fn frequency(&self) -> Vec<Frequency<SI<T>, T>> {
+        self.periodogram
+            .frequency()
+            .into_iter()
+            .map(quantity)
+            .collect()
+    }
+    /// Returns the periodogram values
+    pub fn values(&self) -> Vec<Quantity<D, SI<T>, T>> {
+        self.periodogram[..]
+            .iter()
+            .map(|&value| quantity(value))
+            .collect()
+    }
+}
+
+/// Welch estimator of a signal of quantities of dimension `D`
+///
+/// The signal is given as a slice of `uom` quantities, and the dimension of the signal propagates to the periodograms:
+/// [QuantityWelch::spectral_density] returns a [QuantityPeriodogram] of dimension `D²/Hz`
+/// and [QuantityWelch::power_spectrum] a [QuantityPeriodogram] of dimension `D²`,
+/// whatever the [Scaling] of the [Builder].
+/// The periodograms are computed from the values of the quantities in the SI base units, e.g. `m/s²` for an acceleration.
+/// A [Calibration](crate::Calibration) of the [Builder] changes the unit of the periodograms
+/// and it must not be used with a signal of quantities.
+pub struct QuantityWelch<'a, T: Signal, D: ?Sized, W: Window<T> = Hann<T>> {
+    welch: Welch<'a, T, W>,
+    dimension: PhantomData<D>,
+}
+/// [Builder] of a [QuantityWelch] estimator
+pub struct QuantityBuilder<'a, T: Signal, D: ?Sized, W: Window<T> = Hann<T>> {
+    builder: Builder<'a, T, W>,
+    dimension: PhantomData<D>,
+}
+impl<'a, T, D, W> QuantityWelch<'a, T, D, W>
+where
+    T: Signal + Conversion<T>,
+    SI<T>: Units<T>,
+    W: Window<T>,
+    D: Dimension + SpectralDimension + ?Sized,
+{
+    /// Returns the [QuantityBuilder] given the `signal` sampled at the frequency `fs`
+    pub fn builder(
+        signal: &'a [Quantity<D, SI<T>, T>],
+        fs: Frequency<SI<T>, T>,
+    ) -> QuantityBuilder<'a, T, D, W> {
+        // `Quantity` is `#[repr(transparent)]` over its value in the SI base units
+        let values =
+            unsafe { std::slice::from_raw_parts(signal.as_ptr() as *const T, signal.len()) };
+        QuantityBuilder {
+            builder: Builder::new(values).sampling_frequency(fs.value),
+            dimension: PhantomData,
+        }
+    }
+    /// Returns the [Welch] estimator of the signal values in the SI base units
+    pub fn welch(&self) -> &Welch<'a, T, W> {
+        &self.welch
+    }
+    /// Returns the spectral density of dimension `D²/Hz`
+    pub fn spectral_density(&self) -> QuantityPeriodogram<T, D::Density> {
+        QuantityPeriodogram {
+            periodogram: self.welch.scaled_periodogram(Scaling::Density),
+            dimension: PhantomData,
+        }
+    }
+    /// Returns the power spectrum of dimension `D²`
+    pub fn power_spectrum(&self) -> QuantityPeriodogram<T, D::Spectrum> {
+        QuantityPeriodogram {
+            periodogram: self.welch.scaled_periodogram(Scaling::Spectrum),
+            dimension: PhantomData,
+        }
+    }
+}
+impl<'a, T, D, W> QuantityBuilder<'a, T, D, W>
+where
+    T: Signal + Conversion<T>,
+    SI<T>: Units<T>,
+    W: Window<T>,
+    D: Dimension + ?Sized,
+{
+    /// Applies the `settings` to the [Builder] of the estimator, e.g. `|builder| builder.segment_size(1024)`
+    pub fn settings<F>(self, settings: F) -> Self
+    where
+        F: FnOnce(Builder<'a, T, W>) -> Builder<'a, T, W>,
+    {
+        Self {
+            builder: settings(self.builder),
+            ..self
+        }
+    }
+    /// Returns the estimator or an error if the settings are invalid (see [Builder::try_build])
+    pub fn try_build(&self) -> Result<QuantityWelch<'a, T, D, W>, WelchError> {
+        self.builder.validate().map(|_| self.build())
+    }
+}
+impl<'a, T, D, W> Build<QuantityWelch<'a, T, D, W>> for QuantityBuilder<'a, T, D, W>
+where
+    T: Signal + Conversion<T>,
+    SI<T>: Units<T>,
+    W: Window<T>,
+    D: Dimension + ?Sized,
+{
+    fn build(&self) -> QuantityWelch<'a, T, D, W> {
+        QuantityWelch {
+            welch: self.builder.build(),
+            dimension: PhantomData,
+        }
+    }
+}
+
+// The dimensions are unsized `dyn` types that are neither `Debug` nor `Clone`
+impl<T: Signal, D: ?Sized> Clone for QuantityPeriodogram<T, D> {
+    fn clone(&self) -> Self {
+        Self {
+            periodogram: self.periodogram.clone(),
+            dimension: PhantomData,
+        }
+    }
+}
+impl<T: Signal, D: ?Sized> fmt::Debug for QuantityPeriodogram<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuantityPeriodogram")
+            .field("periodogram", &self.periodogram)
+            .finish()
+    }
+}
+impl<'a, T: Signal, D: ?Sized, W: Window<T>> Clone for QuantityWelch<'a, T, D, W> {
+    fn clone(&self) -> Self {
+        Self {
+            welch: self.welch.clone(),
+            dimension: PhantomData,
+        }
+    }
+}
+impl<'a, T: Signal, D: ?Sized, W: Window<T>> fmt::Debug for QuantityWelch<'a, T, D, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuantityWelch")
+            .field("welch", &self.welch)
+            .finish()
+    }
+}
+impl<'a, T: Signal, D: ?Sized, W: Window<T>> Clone for QuantityBuilder<'a, T, D, W> {
+    fn clone(&self) -> Self {
+        Self {
+            builder: self.builder.clone(),
+            dimension: PhantomData,
+        }
+    }
+}
+impl<'a, T: Signal, D: ?Sized, W: Window<T>> fmt::Debug for QuantityBuilder<'a, T, D, W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuantityBuilder")
+            .field("builder", &self.builder)
+            .finish()
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{Hann, Periodogram, SpectralDensity, WelchAccumulator};
+use wasm_bindgen::prelude::*;
+
+/// JavaScript binding of [SpectralDensity]
+///
+/// The spectral density of a signal sampled at `fs`Hz is estimated with the default settings of [SpectralDensity]
+/// when the estimator is created.
+#[wasm_bindgen(js_name = SpectralDensity)]
+pub struct WasmSpectralDensity {
+    periodogram: Periodogram<f64>,
+}
+#[wasm_bindgen(js_class = SpectralDensity)]
+impl WasmSpectralDensity {
+    /// Estimates the spectral density of the `signal` sampled at `fs`Hz
+    ///
+    /// Throws an error if the settings are invalid (see [WelchError](crate::WelchError))
+    #[wasm_bindgen(constructor)]
+    pub fn new(signal: &[f64], fs: f64) -> Result<WasmSpectralDensity, JsError> {
+        let welch: SpectralDensity<f64> = SpectralDensity::builder(signal, fs).try_build()?;
+        Ok(Self {
+            periodogram: welch.periodogram(),
+        })
+    }
+    /// Returns the frequency vector in Hz
+    pub fn frequency(&self) -> Vec<f64> {
+        self.periodogram.frequency()
+    }
+    /// Returns the spectral density values
+    pub fn values(&self) -> Vec<f64> {
+        self.periodogram.to_vec()
+    }
+}
+/// JavaScript binding of a [WelchAccumulator] with a [Hann] window, for streamed signals
+#[wasm_bindgen(js_name = WelchAccumulator)]
+pub struct WasmWelchAccumulator(WelchAccumulator<f64, Hann<f64>>);
+#[wasm_bindgen(js_class = WelchAccumulator)]
+impl WasmWelchAccumulator {
+    /// Creates a new streaming estimator for a signal sampled at `fs`Hz,
+    /// with segments of size `segment_size` overlapping by a fraction `overlap` (`0<=a<1`)
+    #[wasm_bindgen(constructor)]
+    pub fn new(segment_size: usize, overlap: f64, fs: f64) -> WasmWelchAccumulator {
+        Self(WelchAccumulator::new(segment_size, overlap).sampling_frequency(fs))
+    }
+    /// Pushes new `samples` into the estimator
+    pub fn push(&mut self, samples: &[f64]) {
+        self.0.push(samples);
+    }
+    /// Discards the accumulated segments and the pending samples
+    pub fn reset(&mut self) {
+        self.0.reset();
+    }
+    /// Returns the number of segments accumulated so far
+    #[wasm_bindgen(getter)]
+    pub fn n_segment(&self) -> usize {
+        self.0.n_segment
+    }
+    /// Returns the frequency vector in Hz
+    pub fn frequency(&self) -> Vec<f64> {
+        self.0.spectral_density().frequency()
+    }
+    /// Returns the current spectral density values
+    pub fn spectral_density(&self) -> Vec<f64> {
+        self.0.spectral_density().to_vec()
+    }
+    /// Returns the current power spectrum values
+    pub fn power_spectrum(&self) -> Vec<f64> {
+        self.0.power_spectrum().to_vec()
+    }
+}
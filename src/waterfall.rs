@@ -0,0 +1,123 @@
+use crate::{Periodogram, Sample, Signal, Welch, WelchError, WelchPlan, Window};
+use std::ops::Range;
+
+/// Waterfall of Welch periodograms
+///
+/// The signal is divided into blocks of the same size, either consecutive or overlapping, and the periodogram
+/// of each block is estimated with the Welch method, the [WelchPlan] being shared by all the blocks.
+/// The size of the blocks is the length of the signal the plan is built with,
+/// and the segmentation of the plan applies to each block.
+/// Each block is checked against the [NanPolicy](crate::NanPolicy) of the plan with [WelchPlan::estimate].
+///
+/// Unlike the spectrogram of the segments periodogram ([Welch::segment_periodograms]),
+/// each periodogram of the waterfall is averaged over the segments of a block,
+/// such as the evolution of the periodogram of long records is monitored with a low variance.
+#[derive(Debug, Clone)]
+pub struct Waterfall<'a, T: Signal, W: Window<T>, S: Sample<T> = T> {
+    signal: &'a [S],
+    block_step: usize,
+    plan: WelchPlan<T, W, S>,
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Waterfall<'a, T, W, S> {
+    /// Creates the waterfall of the `signal` with consecutive blocks of the size of the signal the `plan` is built with
+    ///
+    /// Panics if the plan has a validity mask ([Builder::mask](crate::Builder::mask)),
+    /// the mask of the plan applying to a single block and not to the whole signal
+    pub fn new(plan: WelchPlan<T, W, S>, signal: &'a [S]) -> Self {
+        assert!(
+            plan.mask.is_none(),
+            "the plan of the blocks must not have a validity mask"
+        );
+        Self {
+            signal,
+            block_step: plan.signal_len(),
+            plan,
+        }
+    }
+    /// Sets the number of samples between the starting points of 2 consecutive blocks
+    ///
+    /// The blocks overlap if `block_step` is less than the block size
+    pub fn block_step(self, block_step: usize) -> Self {
+        assert!(block_step > 0, "the block step must be greater than 0");
+        Self { block_step, ..self }
+    }
+    /// Returns the [WelchPlan] of the blocks
+    pub fn plan(&self) -> &WelchPlan<T, W, S> {
+        &self.plan
+    }
+    /// Returns the size of the blocks
+    pub fn block_size(&self) -> usize {
+        self.plan.signal_len()
+    }
+    /// Returns the number of blocks
+    pub fn n_block(&self) -> usize {
+        let n = self.block_size();
+        if self.signal.len() < n {
+            0
+        } else {
+            (self.signal.len() - n) / self.block_step + 1
+        }
+    }
+    // Ranges of the samples of each block
+    pub(crate) fn block_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        let n = self.block_size();
+        (0..self.n_block()).map(move |j| j * self.block_step..j * self.block_step + n)
+    }
+    /// Returns an iterator over the [Welch] estimator of each block
+    ///
+    /// The estimator of a block is an error if the block is rejected by [WelchPlan::estimate],
+    /// e.g. if it has NaN samples with [NanPolicy::Error](crate::NanPolicy::Error)
+    pub fn blocks(&self) -> impl Iterator<Item = Result<Welch<'a, T, W, S>, WelchError>> + '_ {
+        let signal = self.signal;
+        self.block_ranges()
+            .map(move |range| self.plan.estimate(&signal[range]))
+    }
+    /// Returns the periodogram of each block, scaled according to [Welch::scaling](Welch#structfield.scaling)
+    ///
+    /// The periodograms are ordered in time, such as they form a time × frequency array.
+    /// Returns the error of the first block rejected by [WelchPlan::estimate]
+    pub fn periodograms(&self) -> Result<Vec<Periodogram<T>>, WelchError> {
+        self.blocks()
+            .map(|welch| welch.map(|welch| welch.periodogram()))
+            .collect()
+    }
+    /// Returns the time in seconds at the center of each block
+    ///
+    /// The time is relative to the first sample of the signal
+    pub fn time(&self) -> Vec<T> {
+        let fs = self.plan.fs * T::from_usize(self.plan.decimation).unwrap();
+        let half = T::from_usize(self.block_size()).unwrap() * T::from_f64(0.5).unwrap();
+        (0..self.n_block())
+            .map(|j| (T::from_usize(j * self.block_step).unwrap() + half) / fs)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Hann, NanPolicy, Waterfall, WelchError, WelchPlan};
+
+    #[test]
+    fn nan_block() {
+        let x: Vec<f64> = (0..4_000).map(|i| (i as f64 * 0.1).sin()).collect();
+        let plan: WelchPlan<f64, Hann<f64>> = WelchPlan::builder(1_000, 1.)
+            .nan_policy(NanPolicy::Error)
+            .build();
+        assert_eq!(
+            Waterfall::new(plan.clone(), &x)
+                .periodograms()
+                .unwrap()
+                .len(),
+            4
+        );
+        let mut y = x.clone();
+        y[2_500] = f64::NAN;
+        let waterfall = Waterfall::new(plan, &y);
+        let blocks: Vec<_> = waterfall.blocks().map(|b| b.is_ok()).collect();
+        assert_eq!(blocks, [true, true, false, true]);
+        assert!(matches!(
+            waterfall.periodograms(),
+            Err(WelchError::NanSample(500))
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{Build, Builder, Hann, Signal, SpectralDensity};
+use hound::{SampleFormat, WavReader};
+use std::{io::Read, path::Path};
+
+/// Signal read from a WAV file
+///
+/// The samples of each channel are de-interleaved and converted to floating point numbers,
+/// integer samples of `b` bits being normalized to `[-1,1)` by dividing them by `2^(b-1)`.
+/// The sampling frequency is read from the file header.
+#[derive(Debug, Clone)]
+pub struct WavSignal<T> {
+    /// the signal sampling frequency `[Hz]`
+    pub fs: T,
+    channels: Vec<Vec<T>>,
+}
+impl<T: Signal> WavSignal<T> {
+    /// Reads the WAV file at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, hound::Error> {
+        Self::from_wav_reader(WavReader::open(path)?)
+    }
+    /// Reads a WAV file from the `reader`
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, hound::Error> {
+        Self::from_wav_reader(WavReader::new(reader)?)
+    }
+    // Converts and de-interleaves the samples of the WAV reader
+    fn from_wav_reader<R: Read>(reader: WavReader<R>) -> Result<Self, hound::Error> {
+        let spec = reader.spec();
+        let n_channel = spec.channels as usize;
+        let samples: Vec<f64> = match spec.sample_format {
+            SampleFormat::Float => reader
+                .into_samples::<f32>()
+                .map(|x| x.map(f64::from))
+                .collect::<Result<_, _>>()?,
+            SampleFormat::Int => {
+                let u = 2f64.powi(spec.bits_per_sample as i32 - 1).recip();
+                reader
+                    .into_samples::<i32>()
+                    .map(|x| x.map(|x| x as f64 * u))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        let channels = (0..n_channel)
+            .map(|i| {
+                samples
+                    .iter()
+                    .skip(i)
+                    .step_by(n_channel)
+                    .map(|&x| T::from_f64(x).unwrap())
+                    .collect()
+            })
+            .collect();
+        Ok(Self {
+            fs: T::from_f64(spec.sample_rate as f64).unwrap(),
+            channels,
+        })
+    }
+    /// Returns the number of channels
+    pub fn n_channel(&self) -> usize {
+        self.channels.len()
+    }
+    /// Returns the samples of the channel `i`
+    pub fn channel(&self, i: usize) -> &[T] {
+        &self.channels[i]
+    }
+    /// Returns the samples of all the channels
+    pub fn channels(&self) -> Vec<&[T]> {
+        self.channels.iter().map(|c| c.as_slice()).collect()
+    }
+    /// Returns a [SpectralDensity] [Builder] for the channel `i` sampled at the file sampling frequency
+    pub fn builder(&self, i: usize) -> Builder<'_, T, Hann<T>> {
+        SpectralDensity::builder(self.channel(i), self.fs)
+    }
+    /// Returns the [SpectralDensity] estimator of the channel `i` with the default settings
+    pub fn spectral_density(&self, i: usize) -> SpectralDensity<'_, T> {
+        self.builder(i).build()
+    }
+}
@@ -1,8 +1,145 @@
-use crate::{Build, Builder, Signal, Window};
+use crate::{
+    chunks::Samples,
+    decimation::decimate,
+    dft::DftPlan,
+    nan::{is_nan, segments_validity},
+    periodogram::one_sided,
+    simd::Vectorized,
+    Build, Builder, Calibration, Detrend, NanPolicy, Periodogram, PowerSpectrumPeriodogram, Sample,
+    Signal, SpectralDensityPeriodogram, WelchError, WelchPlan, Window,
+};
 use num_complex::Complex;
 use num_traits::Zero;
-use rustfft::{algorithm::Radix4, Fft, FftDirection};
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display, sync::Arc};
+
+/// Averaging method of the segments periodogram
+///
+/// For each frequency bin, the [TrimmedMean](Averaging::TrimmedMean) discards a fraction of the lowest and of the highest segment
+/// values before averaging, whereas the [WinsorizedMean](Averaging::WinsorizedMean) replaces them with the nearest remaining values.
+/// Both reduce the contribution of occasional transients to the periodogram,
+/// at the cost of a bias as the segments periodogram values are not symmetrically distributed.
+/// The [MaxHold](Averaging::MaxHold) and [MinHold](Averaging::MinHold) retain the largest and the smallest segment value,
+/// to catch intermittent components and to find the noise floor, respectively.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Averaging {
+    /// arithmetic mean
+    #[default]
+    Mean,
+    /// mean after discarding the given fraction (`0<=x<0.5`) of the lowest and of the highest values
+    TrimmedMean(f64),
+    /// mean after clamping the given fraction (`0<=x<0.5`) of the lowest and of the highest values
+    WinsorizedMean(f64),
+    /// maximum value
+    MaxHold,
+    /// minimum value
+    MinHold,
+}
+impl Averaging {
+    // Averages the `segments` periodogram bin by bin
+    //
+    // The segments are consumed one at a time, except for the trimmed and winsorized means
+    // that require all the segments
+    pub(crate) fn average<T: Signal>(
+        &self,
+        segments: impl Iterator<Item = Vec<T>>,
+        n: usize,
+    ) -> Vec<T> {
+        let x = match *self {
+            Averaging::Mean => {
+                let (k, sum) = segments.fold((0usize, vec![T::zero(); n]), |(k, mut a), p| {
+                    T::accumulate(&mut a, &p);
+                    (k + 1, a)
+                });
+                let kr = T::from_usize(k.max(1)).unwrap().recip();
+                return sum.into_iter().map(|a| a * kr).collect();
+            }
+            Averaging::MaxHold | Averaging::MinHold => {
+                let max = *self == Averaging::MaxHold;
+                return segments
+                    .reduce(|mut a, p| {
+                        a.iter_mut().zip(p).for_each(|(a, p)| {
+                            *a = if max { a.max(p) } else { a.min(p) };
+                        });
+                        a
+                    })
+                    .unwrap_or_else(|| vec![T::zero(); n]);
+            }
+            Averaging::TrimmedMean(x) | Averaging::WinsorizedMean(x) => x,
+        };
+        let segments: Vec<Vec<T>> = segments.collect();
+        let k = segments.len();
+        assert!(
+            (0f64..0.5).contains(&x),
+            "the averaging fraction must be within [0,0.5)"
+        );
+        let q = (x * k as f64).floor() as usize;
+        let mut bin = vec![T::zero(); k];
+        (0..n)
+            .map(|i| {
+                bin.iter_mut().zip(&segments).for_each(|(b, p)| *b = p[i]);
+                bin.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                match *self {
+                    Averaging::WinsorizedMean(_) => {
+                        let (lo, hi) = (bin[q], bin[k - 1 - q]);
+                        bin.iter().map(|&b| b.max(lo).min(hi)).sum::<T>()
+                            / T::from_usize(k).unwrap()
+                    }
+                    _ => {
+                        bin[q..k - q].iter().cloned().sum::<T>() / T::from_usize(k - 2 * q).unwrap()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// Periodogram scaling
+///
+/// Selects the periodogram returned by [Welch::periodogram], see also [SpectralDensityPeriodogram]
+/// and [PowerSpectrumPeriodogram]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Scaling {
+    /// spectral density (signal unit squared per Hertz)
+    #[default]
+    Density,
+    /// power spectrum (signal unit squared)
+    Spectrum,
+    /// root-mean-square amplitude spectrum (signal unit), the square root of the power spectrum
+    ///
+    /// The amplitude of a tone centered on a bin is the tone root-mean-square amplitude
+    /// with the amplitude correction of the window ([WindowCorrection])
+    RmsAmplitude,
+    /// peak amplitude spectrum (signal unit), the root-mean-square amplitude spectrum times `sqrt(2)`
+    ///
+    /// The amplitude of a tone centered on a bin is the tone peak amplitude
+    /// with the amplitude correction of the window ([WindowCorrection]).
+    /// The amplitude at the zero and Nyquist frequencies, and the amplitude of a two-sided periodogram,
+    /// are the root-mean-square amplitudes, i.e. the magnitudes of the complex exponentials
+    PeakAmplitude,
+}
+impl Scaling {
+    // `true` for the amplitude spectra
+    pub(crate) fn is_amplitude(&self) -> bool {
+        matches!(self, Scaling::RmsAmplitude | Scaling::PeakAmplitude)
+    }
+}
+
+/// Window correction of the periodogram
+///
+/// The window attenuates the signal, the attenuation being compensated either for the amplitude of the tones,
+/// with the amplitude-correction factor (ACF), or for the power of the broadband signals,
+/// with the energy-correction factor (ECF), as with the spectrum analyzers.
+/// The correction applies to the spectral density, to the power spectrum and to the amplitude spectra ([Scaling]).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum WindowCorrection {
+    /// energy correction of the spectral density and amplitude correction of the power and amplitude spectra
+    #[default]
+    Auto,
+    /// amplitude correction: the power of a tone centered on a bin is the tone mean square amplitude
+    Amplitude,
+    /// energy correction: the sum of the periodogram over the frequency band is the signal variance
+    Energy,
+}
 
 /// Welch spectral density estimator
 ///
@@ -12,14 +149,30 @@ use std::fmt::Display;
 ///
 /// The minimum number of segment is chosen to be `k=4`, and the segment length is derived
 /// from `l = trunc(n/(k(1-a)+a))`.
+/// Alternatively, the segment length is set with [Builder::segment_size] and the number of segments
+/// is derived from `k=trunc((n-la)/(l(1-a)))`.
+/// The overlap is either a fraction of the segment length or a number of samples set with [Builder::overlap_size].
 ///
-/// Each segment of length `l` is  multiplied by the predetermined window and zero--padded
-/// to the size `m = 2^p` where `p=ceil(log2(l))`.
-/// The maximum allowed value for `p` is 12 (i.e. `m=4096`).
+/// Each segment of length `l` is optionally detrended (see [Detrend]), multiplied by the
+/// predetermined window and zero--padded to the size `m = 2^p` where `p=ceil(log2(l))`,
+/// unless zero-padding is disabled with [Builder::power_of_two_dft] and then `m=l`,
+/// or unless `m` is set with [Builder::dft_size].
+/// The maximum allowed value for `p` is 12 (i.e. `m=4096`) by default, it is set with [Builder::dft_log2_max_size]
+/// or removed with [Builder::unbounded_dft_size].
 /// If with only 4 segments (`k=4`), `l` is greater than 4096, then `l` is set to 4096 and
 /// the increased number of segments is derived from `k=(n-la)/(l(1-a))`.
+/// The achieved frequency resolution is given by [Welch::frequency_resolution]
+/// and the resolution bandwidth of the window by [Welch::resolution_bandwidth].
+/// The discrete Fourier transform is planned once, when the estimator is built.
+///
+/// The periodogram of the segments are averaged according to the [Averaging] method.
+/// The spectrum of real signals is one-sided, unless [Builder::two_sided] is set.
+/// The periodogram is scaled either as a spectral density or as a power spectrum according to the [Scaling],
+/// and corrected for the window according to the [WindowCorrection].
+///
+/// The signal samples are of type `S`, either real (`S=T`) or complex ([Sample]).
 #[derive(Debug, Clone)]
-pub struct Welch<'a, T: Signal, W: Window<T>> {
+pub struct Welch<'a, T: Signal, W: Window<T>, S: Sample<T> = T> {
     /// number of segments (`k`)
     pub n_segment: usize,
     /// size of each segment (`l`)
@@ -27,15 +180,40 @@ pub struct Welch<'a, T: Signal, W: Window<T>> {
     /// size of the discrete Fourier transform (`p`)
     pub dft_size: usize,
     /// overlaps starting points
-    overlap_idx: usize,
-    /// the signal to estimate the spectral density for
-    signal: &'a [T],
+    pub(crate) overlap_idx: usize,
+    /// the signal to estimate the spectral density for, decimated if [Builder::decimation] is set
+    pub(crate) signal: Samples<'a, S>,
     /// the signal sampling frequency `[Hz]`
     pub fs: T,
     /// segments windowing function
     pub window: W,
+    /// segments periodogram averaging method
+    pub averaging: Averaging,
+    /// periodogram scaling
+    pub scaling: Scaling,
+    /// periodogram window correction
+    pub correction: WindowCorrection,
+    /// segments detrending
+    pub detrend: Detrend<T>,
+    /// two-sided spectrum of real signals
+    two_sided: bool,
+    /// signal decimation factor
+    decimation: usize,
+    /// handling of the NaN samples
+    nan_policy: NanPolicy,
+    /// validity of the signal samples, `false` within the gaps
+    mask: Option<Cow<'a, [bool]>>,
+    /// validity of the segments, all the segments being valid if `None`
+    valid: Option<Vec<bool>>,
+    /// signal calibration
+    calibration: Option<Arc<Calibration<T>>>,
+    /// discrete Fourier transform plan
+    plan: DftPlan<T>,
+    /// CUDA GPU plan, if the [Backend::Cuda](crate::Backend) backend is selected
+    #[cfg(feature = "cuda")]
+    cuda: Option<Arc<crate::cuda::CudaPlan>>,
 }
-impl<'a, T: Signal, W: Window<T>> Display for Welch<'a, T, W> {
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Display for Welch<'a, T, W, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Welch spectral density estimator:")?;
         writeln!(f, " - number of segment: {:>6}", self.n_segment)?;
@@ -45,63 +223,477 @@ impl<'a, T: Signal, W: Window<T>> Display for Welch<'a, T, W> {
             " - overlap size     : {:>6}",
             self.segment_size - self.overlap_idx
         )?;
-        write!(f, " - dft size         : {:>6}", self.dft_size)
+        writeln!(f, " - dft size         : {:>6}", self.dft_size)?;
+        writeln!(
+            f,
+            " - resolution       : {:.3e}Hz",
+            self.frequency_resolution().to_f64().unwrap()
+        )?;
+        writeln!(
+            f,
+            " - ENBW             : {:>6.3} bins",
+            self.enbw().to_f64().unwrap()
+        )?;
+        write!(
+            f,
+            " - RBW              : {:.3e}Hz",
+            self.resolution_bandwidth().to_f64().unwrap()
+        )
     }
 }
-impl<'a, T: Signal, W: Window<T>> Build<Welch<'a, T, W>> for Builder<'a, T> {
-    fn build(&self) -> Welch<'a, T, W> {
-        let mut k = self.n_segment;
-        let mut l = self.segment_size;
-        let mut m = l.next_power_of_two();
-        if m > self.dft_max_size {
-            l = self.dft_max_size;
-            let a = self.overlap;
-            k = ((self.signal.len() as f64 - l as f64 * a) / (l as f64 * (1. - a))).trunc()
-                as usize;
-            m = l;
-        }
-        Welch {
-            n_segment: k,
-            segment_size: l,
-            dft_size: m,
-            overlap_idx: l - (l as f64 * self.overlap).round() as usize,
-            signal: self.signal,
-            fs: self.fs.unwrap_or_else(T::one),
-            window: W::new(l),
-        }
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Build<Welch<'a, T, W, S>> for Builder<'a, T, W, S> {
+    fn build(&self) -> Welch<'a, T, W, S> {
+        assert!(
+            self.signal.has_samples(),
+            "the builder has no signal samples, use Builder::accumulate or WelchPlan::estimate"
+        );
+        let plan: WelchPlan<T, W, S> = self.build();
+        Welch::new(&plan, self.signal.clone())
     }
 }
-impl<'a, T: Signal, W: Window<T>> Welch<'a, T, W> {
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
     /// Returns [Welch] [Builder] given the `signal`
-    pub fn builder(signal: &'a [T]) -> Builder<'a, T> {
+    pub fn builder(signal: &'a [S]) -> Builder<'a, T, W, S> {
         Builder::new(signal)
     }
-    // Splits the signal into overlapping segments and applies the window
-    fn windowed_segments(&self) -> Vec<Complex<T>> {
-        let n = self.segment_size;
-        let d = self.overlap_idx;
-        let m = self.dft_size;
+    // Creates the estimator of the `signal` with the settings of the `plan`
+    pub(crate) fn new(plan: &WelchPlan<T, W, S>, signal: Samples<'a, S>) -> Self {
+        assert_eq!(
+            plan.signal_len,
+            signal.len(),
+            "the signal must have the same length than the signal of the plan"
+        );
+        let mask = plan.mask.as_deref();
+        let signal = plan.nan_policy.apply(&signal, mask);
+        let valid = segments_validity(
+            &signal,
+            mask,
+            plan.nan_policy == NanPolicy::Skip,
+            (plan.segment_size, plan.overlap_idx, plan.decimation),
+        );
+        Welch {
+            n_segment: valid_count(&valid).unwrap_or(plan.n_segment),
+            segment_size: plan.segment_size,
+            dft_size: plan.dft_size,
+            overlap_idx: plan.overlap_idx,
+            signal: decimated(&signal, plan.decimation),
+            fs: plan.fs,
+            window: plan.window.clone(),
+            averaging: plan.averaging,
+            scaling: plan.scaling,
+            correction: plan.correction,
+            detrend: plan.detrend.clone(),
+            two_sided: plan.two_sided,
+            decimation: plan.decimation,
+            nan_policy: plan.nan_policy,
+            mask: plan.mask.clone().map(Cow::Owned),
+            valid,
+            calibration: plan.calibration.clone(),
+            plan: plan.plan.clone(),
+            #[cfg(feature = "cuda")]
+            cuda: plan.cuda.clone(),
+        }
+    }
+    /// Returns a [Welch] estimator with the same settings than `self` for another `signal`
+    ///
+    /// The window of `self` is reused, such as multiple channels can be processed without
+    /// building a new estimator for each channel.
+    /// The `signal` must have the same length than the signal of `self`, and it is decimated as the signal of `self`.
+    /// The NaN samples of the `signal` are handled according to the [NanPolicy] of `self`,
+    /// and the gaps of the `signal` are given by the validity mask of `self` ([Builder::mask]).
+    ///
+    /// Returns an error if the `signal` has NaN samples with [NanPolicy::Error] or if all its segments are invalid
+    pub fn with_signal(&self, signal: &'a [S]) -> Result<Self, WelchError> {
+        if let Some(mask) = self.mask.as_deref() {
+            assert_eq!(
+                mask.len(),
+                signal.len(),
+                "the signal must have the same length than the validity mask"
+            );
+        }
+        let signal: Samples<'a, S> = signal.into();
+        self.nan_policy.check(
+            &signal,
+            self.mask.as_deref(),
+            (self.segment_size, self.overlap_idx, self.decimation),
+        )?;
+        let signal = self.nan_policy.apply(&signal, self.mask.as_deref());
+        let valid = segments_validity(
+            &signal,
+            self.mask.as_deref(),
+            self.nan_policy == NanPolicy::Skip,
+            (self.segment_size, self.overlap_idx, self.decimation),
+        );
+        let signal = decimated(&signal, self.decimation);
+        assert_eq!(
+            self.signal.len(),
+            signal.len(),
+            "the signals must have the same length"
+        );
+        Ok(Self {
+            n_segment: valid_count(&valid).unwrap_or(self.n_segment),
+            signal,
+            valid,
+            ..self.clone()
+        })
+    }
+    // Missing samples of the signal, within the gaps of the validity mask or NaN with [NanPolicy::Skip],
+    // all the samples being valid if `None` is returned
+    //
+    // A sample of a decimated signal is missing if any of the `factor` samples it is decimated from is within a gap
+    pub(crate) fn missing_samples(&self) -> Option<Vec<bool>> {
+        let skip_nan = self.nan_policy == NanPolicy::Skip;
+        if self.mask.is_none() && !skip_nan {
+            return None;
+        }
+        let f = self.decimation;
+        let mask = self.mask.as_deref();
+        Some(
+            self.signal
+                .iter()
+                .enumerate()
+                .map(|(j, x)| {
+                    mask.is_some_and(|m| m[j * f..((j + 1) * f).min(m.len())].contains(&false))
+                        || (skip_nan && is_nan(x))
+                })
+                .collect(),
+        )
+    }
+    // Restricts the segments of `self` and of `other` to the segments valid for both estimators
+    pub(crate) fn common_segments(&mut self, other: &mut Self) {
+        let valid = match (&self.valid, &other.valid) {
+            (None, None) => return,
+            (Some(v), None) | (None, Some(v)) => v.clone(),
+            (Some(a), Some(b)) => a.iter().zip(b).map(|(&a, &b)| a && b).collect(),
+        };
+        let k = valid.iter().filter(|&&v| v).count();
+        (self.n_segment, other.n_segment) = (k, k);
+        (self.valid, other.valid) = (Some(valid.clone()), Some(valid));
+    }
+    /// Returns the periodogram scaled according to [Welch::scaling](Welch#structfield.scaling)
+    pub fn periodogram(&self) -> Periodogram<T> {
+        self.scaled_periodogram(self.scaling)
+    }
+    /// Returns the periodogram scaled according to `scaling`
+    pub fn scaled_periodogram(&self, scaling: Scaling) -> Periodogram<T> {
+        match scaling {
+            Scaling::Density => SpectralDensityPeriodogram::periodogram(self),
+            Scaling::Spectrum => PowerSpectrumPeriodogram::periodogram(self),
+            Scaling::RmsAmplitude | Scaling::PeakAmplitude => {
+                self.amplitude_spectrum(scaling, PowerSpectrumPeriodogram::periodogram(self))
+            }
+        }
+    }
+    /// Returns the periodogram of each segment, before averaging, scaled according to [Welch::scaling](Welch#structfield.scaling)
+    pub fn segment_periodograms(&self) -> Vec<Periodogram<T>> {
+        match self.scaling {
+            Scaling::Density => SpectralDensityPeriodogram::segment_periodograms(self),
+            Scaling::Spectrum => PowerSpectrumPeriodogram::segment_periodograms(self),
+            Scaling::RmsAmplitude | Scaling::PeakAmplitude => {
+                PowerSpectrumPeriodogram::segment_periodograms(self)
+                    .into_iter()
+                    .map(|p| self.amplitude_spectrum(self.scaling, p))
+                    .collect()
+            }
+        }
+    }
+    /// Returns the frequency resolution `fs/m` in Hz
+    pub fn frequency_resolution(&self) -> T {
+        self.fs / T::from_usize(self.dft_size).unwrap()
+    }
+    /// Returns the equivalent noise bandwidth of the window in bins of width `fs/l`
+    ///
+    /// See [Window::enbw]
+    pub fn enbw(&self) -> T {
+        self.window.enbw()
+    }
+    /// Returns the resolution bandwidth in Hz, the equivalent noise bandwidth of the window times `fs/l`
+    ///
+    /// The resolution bandwidth does not depend on the zero-padding of the segments,
+    /// unlike the [frequency resolution](Welch::frequency_resolution)
+    pub fn resolution_bandwidth(&self) -> T {
+        self.enbw() * self.fs / T::from_usize(self.segment_size).unwrap()
+    }
+    /// Returns `true` if the spectrum is two-sided
+    ///
+    /// The spectrum is two-sided for complex signals or if it is set with [Builder::two_sided]
+    pub fn is_two_sided(&self) -> bool {
+        S::COMPLEX || self.two_sided
+    }
+    // Number of frequency bins: from the zero to the Nyquist frequency (`m/2+1`) for one-sided spectrum
+    // and the whole DFT for two-sided spectrum
+    pub(crate) fn n_bins(&self) -> usize {
+        if self.is_two_sided() {
+            self.dft_size
+        } else {
+            self.dft_size / 2 + 1
+        }
+    }
+    // Multiplies the periodogram `values`, in the order of the discrete Fourier transform,
+    // by the power gain of the calibration at the frequency of each bin
+    pub(crate) fn calibrated<V>(&self, mut values: Vec<V>) -> Vec<V>
+    where
+        V: Copy + std::ops::Mul<T, Output = V>,
+    {
+        if let Some(calibration) = &self.calibration {
+            let n = values.len();
+            let df = self.frequency_resolution();
+            values.iter_mut().enumerate().for_each(|(k, x)| {
+                let f = if self.is_two_sided() && 2 * k >= n {
+                    -T::from_usize(n - k).unwrap() * df
+                } else {
+                    T::from_usize(k).unwrap() * df
+                };
+                *x = *x * calibration.gain(f);
+            });
+        }
+        values
+    }
+    // Creates a one-sided or a two-sided [Periodogram]
+    pub(crate) fn to_periodogram<V>(&self, values: Vec<V>) -> Periodogram<T, V> {
+        if self.is_two_sided() {
+            Periodogram::two_sided(self.fs, values)
+        } else {
+            Periodogram::from_values(self.fs, self.dft_size, values)
+        }
+    }
+    /// Returns the coherent average of the segments discrete Fourier transform
+    ///
+    /// The complex spectra of the segments are averaged, preserving the phase and reducing
+    /// the contribution of the components that are not synchronous with the segments.
+    /// The spectrum is scaled by the sum of the window weights, such as a sinusoid of amplitude `A`
+    /// at the frequency of a bin has a magnitude `A/2`
+    pub fn coherent_average(&self) -> Periodogram<T, Complex<T>> {
+        let n = self.n_bins();
+        let (k, sum) = self
+            .dfts()
+            .fold((0usize, vec![Complex::zero(); n]), |(k, mut a), dft| {
+                a.iter_mut().zip(dft).for_each(|(a, x)| *a = *a + x);
+                (k + 1, a)
+            });
+        let u = (self.window.sum_sqr().sqrt() * T::from_usize(k.max(1)).unwrap()).recip();
+        self.to_periodogram(sum.into_iter().map(|x| x * u).collect())
+    }
+    /// Returns the equivalent degrees of freedom of the periodogram averaged with the arithmetic mean
+    ///
+    /// The degrees of freedom are given by Welch and Percival formula `v=2k/(1+2 sum_{j=1}^{k-1} (1-j/k) r(j)^2)`,
+    /// where `k` is the number of segments and `r(j)` is the correlation of the window with itself shifted
+    /// by `j` times the distance between the segments starting points.
+    /// Without overlap, `v=2k`, and with the 50% overlap of a [Hann](crate::Hann) window, `v≈1.89k` for large `k`.
+    /// The periodogram at each frequency is distributed as `P χ²(v)/v`, `P` being the true periodogram.
+    pub fn degrees_of_freedom(&self) -> T {
+        let k = self.n_segment;
+        T::from_usize(2 * k).unwrap()
+            / overlap_correlation(self.window.weights(), k, self.overlap_idx)
+    }
+    // Average of the segments periodogram, computed on the GPU with the CUDA backend
+    pub(crate) fn averaged_power(&self) -> Vec<T> {
+        #[cfg(feature = "cuda")]
+        if let Some(cuda) = &self.cuda {
+            return self.cuda_averaged_power(cuda);
+        }
+        self.averaging.average(self.segments_power(), self.n_bins())
+    }
+    // Periodogram of each segment, computed on the GPU with the CUDA backend
+    pub(crate) fn segments_periodogram(&self) -> Vec<Vec<T>> {
+        #[cfg(feature = "cuda")]
+        if let Some(cuda) = &self.cuda {
+            return self.cuda_segments_power(cuda);
+        }
+        self.segments_power().collect()
+    }
+    // Squared magnitude of each segment discrete Fourier transform
+    pub(crate) fn segments_power(&self) -> impl Iterator<Item = Vec<T>> + '_ {
+        self.segments_power_with(self.window.weights())
+    }
+    // Squared magnitude of each segment discrete Fourier transform with the window `weights`
+    //
+    // The positive frequencies of one-sided spectrum are doubled to account for the negative frequencies
+    pub(crate) fn segments_power_with<'b>(
+        &'b self,
+        weights: &'b [T],
+    ) -> impl Iterator<Item = Vec<T>> + 'b {
+        let n = self.n_bins();
+        self.dfts_with(weights).map(move |dft| {
+            let mut p = T::norm_sqr(&dft[..n.min(dft.len())]);
+            if !self.is_two_sided() {
+                one_sided(&mut p, self.dft_size);
+            }
+            p
+        })
+    }
+    // Scaling of the squared magnitude of the segments Fourier transform according to `scaling`,
+    // corrected for the window according to [Welch::correction](Welch#structfield.correction) (see [segments_scale])
+    pub(crate) fn scale(&self, scaling: Scaling) -> T {
+        self.scale_with(scaling, &self.window)
+    }
+    // Scaling of the squared magnitude of the segments Fourier transform windowed with `window` (see [Welch::scale])
+    pub(crate) fn scale_with<V: Window<T>>(&self, scaling: Scaling, window: &V) -> T {
+        segments_scale(scaling, self.correction, window, self.dft_size, self.fs)
+    }
+    // Amplitude of the power spectrum value `x` at the frequency `f` according to the amplitude `scaling`
+    pub(crate) fn amplitude(&self, scaling: Scaling, f: T, x: T) -> T {
+        amplitude(scaling, self.is_two_sided(), self.fs, f, x)
+    }
+    // Amplitude spectrum of the power spectrum `periodogram` according to the amplitude `scaling`
+    pub(crate) fn amplitude_spectrum(
+        &self,
+        scaling: Scaling,
+        periodogram: Periodogram<T>,
+    ) -> Periodogram<T> {
+        amplitude_spectrum(scaling, periodogram)
+    }
+    // Scaling of the squared magnitude of the segments Fourier transform at the frequency `f`,
+    // including the power gain of the calibration
+    //
+    // The frequencies of a one-sided periodogram, except the zero and Nyquist frequencies, are doubled
+    pub(crate) fn power_scale(&self, f: T) -> T {
+        let u =
+            self.scale(self.scaling) * self.calibration.as_ref().map_or(T::one(), |c| c.gain(f));
+        if self.is_two_sided() || f <= T::zero() || f + f >= self.fs {
+            u
+        } else {
+            u + u
+        }
+    }
+    // Signal segments, without the invalid segments
+    fn segments(&self) -> impl Iterator<Item = Cow<'_, [S]>> {
         self.signal
-            .windows(n)
-            .step_by(d)
-            .flat_map(|s| {
-                let mut buffer: Vec<Complex<T>> = vec![Complex::zero(); m];
-                s.iter()
-                    .zip(self.window.weights())
-                    .map(|(&x, &w)| x * w)
-                    .zip(&mut buffer)
-                    .for_each(|(v, c)| {
-                        c.re = v;
-                    });
-                buffer
-            })
-            .collect()
+            .segments(self.segment_size, self.overlap_idx)
+            .enumerate()
+            .filter(|(j, _)| self.valid.as_ref().is_none_or(|v| v[*j]))
+            .map(|(_, s)| s)
+    }
+    // Index of the first sample of each segment, without the invalid segments
+    #[cfg(feature = "hdf5")]
+    pub(crate) fn segment_starts(&self) -> impl Iterator<Item = usize> + '_ {
+        let n = (self.signal.len() + self.overlap_idx).saturating_sub(self.segment_size)
+            / self.overlap_idx;
+        (0..n)
+            .filter(|&j| self.valid.as_ref().is_none_or(|v| v[j]))
+            .map(|j| j * self.overlap_idx)
+    }
+    // Detrended and windowed segments
+    pub(crate) fn windowed_segments(&self) -> impl Iterator<Item = Vec<Complex<T>>> + '_ {
+        let weights = self.window.weights();
+        self.segments().map(move |s| {
+            let s: Vec<Complex<T>> = if self.detrend.is_some() {
+                self.detrend.apply(&s)
+            } else {
+                s.iter().map(|x| x.to_complex()).collect()
+            };
+            s.into_iter().zip(weights).map(|(x, &w)| x * w).collect()
+        })
     }
     // Fourier transform each segment
-    pub(crate) fn dfts(&self) -> Vec<Complex<T>> {
-        let mut buffer = self.windowed_segments();
-        let fft = Radix4::new(self.dft_size, FftDirection::Forward);
-        fft.process(&mut buffer);
-        buffer
+    pub(crate) fn dfts(&self) -> impl Iterator<Item = Vec<Complex<T>>> + '_ {
+        self.dfts_with(self.window.weights())
+    }
+    // Fourier transform each segment windowed with `weights`
+    //
+    // The segments are windowed and Fourier transformed one at a time, when the iterator is consumed
+    pub(crate) fn dfts_with<'b>(
+        &'b self,
+        weights: &'b [T],
+    ) -> impl Iterator<Item = Vec<Complex<T>>> + 'b {
+        let mut scratch = self.plan.make_scratch_vec();
+        self.segments().map(move |s| {
+            if self.detrend.is_some() {
+                self.plan
+                    .process(&self.detrend.apply(&s), weights, &mut scratch)
+            } else {
+                self.plan.process(&s, weights, &mut scratch)
+            }
+        })
+    }
+}
+// Number of valid segments
+fn valid_count(valid: &Option<Vec<bool>>) -> Option<usize> {
+    valid.as_ref().map(|v| v.iter().filter(|&&v| v).count())
+}
+// Borrows the `signal` or decimates it by `factor`
+fn decimated<'a, T: Signal, S: Sample<T>>(
+    signal: &Samples<'a, S>,
+    factor: usize,
+) -> Samples<'a, S> {
+    if factor > 1 {
+        decimate(&signal.contiguous(), factor).into()
+    } else {
+        signal.clone()
+    }
+}
+// Correlation factor `1+2 sum_{j=1}^{k-1} (1-j/k) r(j)^2` of the degrees of freedom of `k` segments `d` samples apart,
+// `r(j)` being the correlation of the window `w` with itself shifted by `j d` samples
+// (see [Welch::degrees_of_freedom])
+pub(crate) fn overlap_correlation<T: Signal>(w: &[T], k: usize, d: usize) -> T {
+    let sqr_sum = w.iter().map(|&w| w * w).sum::<T>();
+    let s = (1..k)
+        .take_while(|j| j * d < w.len())
+        .map(|j| {
+            let r = w.iter().zip(&w[j * d..]).map(|(&a, &b)| a * b).sum::<T>() / sqr_sum;
+            (T::one() - T::from_usize(j).unwrap() / T::from_usize(k).unwrap()) * r * r
+        })
+        .sum::<T>();
+    T::one() + s + s
+}
+// Scaling of the squared magnitude of the discrete Fourier transform of size `m` of the segments windowed with `window`
+// and sampled at `fs`, according to `scaling` and corrected for the window according to `correction`
+//
+// The energy-corrected power spectrum is the energy-corrected spectral density times the bin width `fs/m`,
+// and the amplitude-corrected spectral density is the amplitude-corrected power spectrum divided by the bin width
+//
+// The amplitude spectra are scaled as the power spectrum, before the square root
+pub(crate) fn segments_scale<T: Signal, W: Window<T>>(
+    scaling: Scaling,
+    correction: WindowCorrection,
+    window: &W,
+    m: usize,
+    fs: T,
+) -> T {
+    let scaling = if scaling.is_amplitude() {
+        Scaling::Spectrum
+    } else {
+        scaling
+    };
+    let m = T::from_usize(m).unwrap();
+    let correction = match correction {
+        WindowCorrection::Auto => match scaling {
+            Scaling::Density => WindowCorrection::Energy,
+            _ => WindowCorrection::Amplitude,
+        },
+        correction => correction,
+    };
+    match (scaling, correction) {
+        (Scaling::Density, WindowCorrection::Amplitude) => m / (window.sum_sqr() * fs),
+        (Scaling::Spectrum, WindowCorrection::Energy) => (window.sqr_sum() * m).recip(),
+        (Scaling::Density, _) => (window.sqr_sum() * fs).recip(),
+        (_, _) => window.sum_sqr().recip(),
+    }
+}
+// Amplitude of the power spectrum value `x` at the frequency `f` according to the amplitude `scaling`
+//
+// The power spectrum of a one-sided periodogram is doubled, except at the zero and Nyquist frequencies,
+// and it is the mean square amplitude of the tones
+fn amplitude<T: Signal>(scaling: Scaling, two_sided: bool, fs: T, f: T, x: T) -> T {
+    match scaling {
+        Scaling::PeakAmplitude if !two_sided && f > T::zero() && f + f < fs => (x + x).sqrt(),
+        Scaling::RmsAmplitude | Scaling::PeakAmplitude => x.sqrt(),
+        Scaling::Density | Scaling::Spectrum => x,
+    }
+}
+// Amplitude spectrum of the power spectrum `periodogram` according to the amplitude `scaling`
+pub(crate) fn amplitude_spectrum<T: Signal>(
+    scaling: Scaling,
+    periodogram: Periodogram<T>,
+) -> Periodogram<T> {
+    if !scaling.is_amplitude() {
+        return periodogram;
     }
+    let (two_sided, fs) = (periodogram.is_two_sided(), periodogram.fs());
+    periodogram.with_values(
+        periodogram
+            .iter()
+            .map(|(f, x)| amplitude(scaling, two_sided, fs, f, x))
+            .collect(),
+    )
 }
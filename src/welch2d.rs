@@ -0,0 +1,211 @@
+use crate::{welch::overlap_correlation, Detrend, Hann, Periodogram2d, Signal, Symmetry, Window};
+use num_complex::Complex;
+use rustfft::FftPlanner;
+use std::marker::PhantomData;
+
+/// Welch spectral density estimator of 2D arrays
+///
+/// The 2D array of `(n_y,n_x)` samples in row major order, e.g. an image or a phase screen, is divided into tiles of
+/// `(l_y,l_x)` samples, each tile overlapping a fraction `a` of the previous tile along both axes.
+/// Each tile is optionally detrended, multiplied by the 2D window, the outer product of the windows of size `l_y` and `l_x`,
+/// and Fourier transformed, and the squared magnitudes of the 2D discrete Fourier transforms are averaged over the tiles.
+/// The periodogram is scaled as a two-sided spectral density (signal unit squared per unit of spatial frequency squared),
+/// such as its sum times the area of the frequency bins is the mean square of the samples.
+///
+/// By default, the tiles are the size of 4 tiles with 50% overlap along each axis,
+/// the array is sampled at 1 sample per unit length along both axes and the tiles are not detrended.
+#[derive(Debug, Clone)]
+pub struct Welch2d<'a, T: Signal, W: Window<T> = Hann<T>> {
+    data: &'a [T],
+    shape: (usize, usize),
+    tile_size: (usize, usize),
+    overlap: f64,
+    fs: (T, T),
+    detrend: Detrend<T>,
+    window: PhantomData<W>,
+}
+impl<'a, T: Signal, W: Window<T>> Welch2d<'a, T, W> {
+    /// Creates a new estimator for the 2D array of `shape` `(n_y,n_x)` with the samples `data` in row major order
+    pub fn new(data: &'a [T], shape: (usize, usize)) -> Self {
+        assert_eq!(
+            data.len(),
+            shape.0 * shape.1,
+            "the number of samples must match the array shape"
+        );
+        assert!(
+            shape.0 > 0 && shape.1 > 0,
+            "the array must have at least 1 sample along each axis"
+        );
+        let tile = |n: usize| ((n as f64 / 2.5) as usize).max(1);
+        Self {
+            data,
+            shape,
+            tile_size: (tile(shape.0), tile(shape.1)),
+            overlap: 0.5,
+            fs: (T::one(), T::one()),
+            detrend: Detrend::None,
+            window: PhantomData,
+        }
+    }
+    /// Sets the size `(l_y,l_x)` of the tiles
+    pub fn tile_size(self, l_y: usize, l_x: usize) -> Self {
+        assert!(
+            (1..=self.shape.0).contains(&l_y) && (1..=self.shape.1).contains(&l_x),
+            "the tiles must be within the array"
+        );
+        Self {
+            tile_size: (l_y, l_x),
+            ..self
+        }
+    }
+    /// Sets the fraction of overlap `a` of the tiles along both axes, within `[0,1)`
+    pub fn overlap(self, overlap: f64) -> Self {
+        assert!(
+            (0f64..1.).contains(&overlap),
+            "the overlap must be within [0,1)"
+        );
+        Self { overlap, ..self }
+    }
+    /// Sets the sampling frequencies `(fs_y,fs_x)` along both axes, the inverse of the sample spacings
+    pub fn sampling_frequency(self, fs_y: T, fs_x: T) -> Self {
+        Self {
+            fs: (fs_y, fs_x),
+            ..self
+        }
+    }
+    /// Sets the tiles [Detrend]
+    ///
+    /// [Detrend::Constant] removes the tile mean and [Detrend::Linear] the least-squares plane fitted to the tile.
+    /// A [Detrend::Custom] closure detrends the tile samples in row major order.
+    /// Panics for polynomials of order greater than 1.
+    pub fn detrend(self, detrend: Detrend<T>) -> Self {
+        let detrend = match detrend {
+            Detrend::Polynomial(0) => Detrend::Constant,
+            Detrend::Polynomial(1) => Detrend::Linear,
+            Detrend::Polynomial(order) => {
+                panic!("2D polynomial detrending of order {order} is not supported")
+            }
+            detrend => detrend,
+        };
+        Self { detrend, ..self }
+    }
+    /// Returns the number of tiles `(k_y,k_x)` along each axis
+    pub fn n_tile(&self) -> (usize, usize) {
+        let (sy, sx) = self.step();
+        (
+            (self.shape.0 - self.tile_size.0) / sy + 1,
+            (self.shape.1 - self.tile_size.1) / sx + 1,
+        )
+    }
+    // Number of samples between the starting points of 2 consecutive tiles along each axis
+    fn step(&self) -> (usize, usize) {
+        let step = |l: usize| (l - (l as f64 * self.overlap).round() as usize).max(1);
+        (step(self.tile_size.0), step(self.tile_size.1))
+    }
+    // Windows along each axis
+    fn windows(&self) -> (W, W) {
+        let (ly, lx) = self.tile_size;
+        (
+            W::with_symmetry(ly, Symmetry::default()),
+            W::with_symmetry(lx, Symmetry::default()),
+        )
+    }
+    // Detrends the `tile` of `(l_y,l_x)` samples in place
+    fn detrend_tile(&self, tile: &mut [T]) {
+        let (ly, lx) = self.tile_size;
+        let n = T::from_usize(ly * lx).unwrap();
+        match &self.detrend {
+            Detrend::None | Detrend::Polynomial(_) => (),
+            Detrend::Constant => {
+                let mean = tile.iter().fold(T::zero(), |s, &x| s + x) / n;
+                tile.iter_mut().for_each(|x| *x -= mean);
+            }
+            Detrend::Linear => {
+                // centered coordinates are orthogonal to each other and to the constant
+                let center = |i: usize, l: usize| {
+                    T::from_usize(i).unwrap()
+                        - T::from_usize(l - 1).unwrap() * T::from_f64(0.5).unwrap()
+                };
+                let (mut sum, mut zy, mut yy, mut zx, mut xx) =
+                    (T::zero(), T::zero(), T::zero(), T::zero(), T::zero());
+                tile.iter().enumerate().for_each(|(k, &z)| {
+                    let (y, x) = (center(k / lx, ly), center(k % lx, lx));
+                    sum += z;
+                    zy += z * y;
+                    yy += y * y;
+                    zx += z * x;
+                    xx += x * x;
+                });
+                let mean = sum / n;
+                let slope = |zs: T, ss: T| if ss > T::zero() { zs / ss } else { T::zero() };
+                let (by, bx) = (slope(zy, yy), slope(zx, xx));
+                tile.iter_mut().enumerate().for_each(|(k, z)| {
+                    *z -= mean + by * center(k / lx, ly) + bx * center(k % lx, lx);
+                });
+            }
+            Detrend::Custom(f) => f(tile),
+        }
+    }
+    /// Returns the 2D spectral density
+    pub fn periodogram(&self) -> Periodogram2d<T> {
+        let nx = self.shape.1;
+        let (ly, lx) = self.tile_size;
+        let (ky, kx) = self.n_tile();
+        let (sy, sx) = self.step();
+        let (wy, wx) = self.windows();
+        let mut planner = FftPlanner::new();
+        let (fft_y, fft_x) = (planner.plan_fft_forward(ly), planner.plan_fft_forward(lx));
+        let mut tile = vec![T::zero(); ly * lx];
+        let mut buffer = vec![Complex::new(T::zero(), T::zero()); ly * lx];
+        let mut transposed = buffer.clone();
+        let mut power = vec![T::zero(); ly * lx];
+        for (iy, ix) in (0..ky).flat_map(|iy| (0..kx).map(move |ix| (iy, ix))) {
+            tile.chunks_mut(lx).enumerate().for_each(|(i, row)| {
+                let k = (iy * sy + i) * nx + ix * sx;
+                row.copy_from_slice(&self.data[k..k + lx]);
+            });
+            self.detrend_tile(&mut tile);
+            buffer
+                .iter_mut()
+                .zip(&tile)
+                .enumerate()
+                .for_each(|(k, (b, &z))| {
+                    *b = Complex::new(z * wy.weights()[k / lx] * wx.weights()[k % lx], T::zero())
+                });
+            // discrete Fourier transform of the rows, then of the columns
+            fft_x.process(&mut buffer);
+            transpose(&buffer, &mut transposed, ly, lx);
+            fft_y.process(&mut transposed);
+            transpose(&transposed, &mut buffer, lx, ly);
+            power
+                .iter_mut()
+                .zip(&buffer)
+                .for_each(|(p, b)| *p += b.norm_sqr());
+        }
+        let u =
+            (T::from_usize(ky * kx).unwrap() * wy.sqr_sum() * wx.sqr_sum() * self.fs.0 * self.fs.1)
+                .recip();
+        // zero frequency at the center
+        let values = (0..ly * lx)
+            .map(|k| {
+                let (i, j) = ((k / lx + ly - ly / 2) % ly, (k % lx + lx - lx / 2) % lx);
+                power[i * lx + j] * u
+            })
+            .collect();
+        // the correlation of the 2D tiles is the product of the correlations along each axis
+        let (ry, rx) = (
+            overlap_correlation(wy.weights(), ky, sy),
+            overlap_correlation(wx.weights(), kx, sx),
+        );
+        Periodogram2d::new(self.fs, (ly, lx), values)
+            .with_dof(T::from_usize(2 * ky * kx).unwrap() / (ry * rx))
+    }
+}
+// Transposes the `(rows,cols)` array `a` into the `(cols,rows)` array `b`
+fn transpose<T: Copy>(a: &[T], b: &mut [T], rows: usize, cols: usize) {
+    for i in 0..rows {
+        for j in 0..cols {
+            b[j * rows + i] = a[i * cols + j];
+        }
+    }
+}
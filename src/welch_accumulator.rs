@@ -0,0 +1,348 @@
+use crate::{
+    dft::DftPlan,
+    periodogram::one_sided,
+    simd::Vectorized,
+    welch::{amplitude_spectrum, segments_scale},
+    Averaging, Backend, Build, Builder, Detrend, NanPolicy, Periodogram, Sample, Scaling, Signal,
+    Symmetry, WelchError, Window, WindowCorrection,
+};
+use std::fmt::Display;
+
+/// Streaming Welch estimator
+///
+/// The signal is pushed incrementally with [WelchAccumulator::push], in chunks of arbitrary size,
+/// or from an iterator with [Extend::extend].
+/// The accumulator is the estimator of the signals given by an iterator, e.g. decoded on the fly,
+/// and it is either created with [WelchAccumulator::new] or built from a [Builder],
+/// e.g. with [Builder::accumulate] for a [Builder] created from the signal length with [Builder::from_len].
+/// Each time enough samples are available, a new segment of length `l` is optionally detrended, windowed,
+/// zero--padded to the size `m = 2^p` where `p=ceil(log2(l))` (or set with [WelchAccumulator::dft_size])
+/// and Fourier transformed with a plan created once,
+/// and its squared magnitude is added to the running sum of the segments periodogram.
+/// The samples overlapping the next segment are kept internally.
+/// The averaged periodogram is available at any time with [WelchAccumulator::periodogram],
+/// scaled according to the [Scaling] and corrected for the window according to the [WindowCorrection]
+/// as the periodogram of a [Welch](crate::Welch) estimator with the same settings,
+/// or with [WelchAccumulator::spectral_density] and [WelchAccumulator::power_spectrum].
+///
+/// The signal samples are of type `S`, either real (`S=T`) or complex ([Sample]).
+#[derive(Debug, Clone)]
+pub struct WelchAccumulator<T: Signal, W: Window<T>, S: Sample<T> = T> {
+    /// number of segments accumulated so far (`k`)
+    pub n_segment: usize,
+    /// size of each segment (`l`)
+    pub segment_size: usize,
+    /// size of the discrete Fourier transform (`p`)
+    pub dft_size: usize,
+    /// overlaps starting points
+    overlap_idx: usize,
+    /// the signal sampling frequency `[Hz]`
+    pub fs: T,
+    /// segments windowing function
+    pub window: W,
+    /// periodogram scaling
+    pub scaling: Scaling,
+    /// periodogram window correction
+    pub correction: WindowCorrection,
+    /// segments detrending
+    pub detrend: Detrend<T>,
+    // two-sided spectrum of real signals
+    two_sided: bool,
+    // samples not yet processed
+    tail: Vec<S>,
+    // sum of the segments periodogram
+    sum: Vec<T>,
+    // discrete Fourier transform plan
+    plan: DftPlan<T>,
+}
+impl<T: Signal, W: Window<T>, S: Sample<T>> WelchAccumulator<T, W, S> {
+    /// Creates a new streaming estimator with segments of size `segment_size` overlapping by a fraction `overlap` (`0<=a<1`)
+    ///
+    /// Panics if the segment size is 0
+    pub fn new(segment_size: usize, overlap: f64) -> Self {
+        assert!(segment_size > 0, "the segment size must be greater than 0");
+        assert!(
+            (0f64..1.).contains(&overlap),
+            "the segment overlapping fraction must be within [0,1)"
+        );
+        let l = segment_size;
+        let m = l.next_power_of_two();
+        let n = if S::COMPLEX { m } else { m / 2 + 1 };
+        Self {
+            n_segment: 0,
+            segment_size: l,
+            dft_size: m,
+            overlap_idx: (l - (l as f64 * overlap).round() as usize).max(1),
+            fs: T::one(),
+            window: W::with_symmetry(l, Symmetry::default()),
+            scaling: Scaling::default(),
+            correction: WindowCorrection::default(),
+            detrend: Detrend::default(),
+            two_sided: false,
+            tail: Vec::with_capacity(l),
+            sum: vec![T::zero(); n],
+            plan: DftPlan::new(m, S::COMPLEX),
+        }
+    }
+    /// Sets the signal sampling frequency
+    pub fn sampling_frequency(self, fs: T) -> Self {
+        Self { fs, ..self }
+    }
+    /// Sets the size of the discrete Fourier transform (`m`)
+    ///
+    /// The segments are zero-padded to `m` samples, `m` must be greater or equal to the segment size.
+    /// The segments already accumulated are discarded.
+    pub fn dft_size(self, dft_size: usize) -> Self {
+        assert!(
+            dft_size >= self.segment_size,
+            "the DFT size ({}) must be greater or equal to the segment size ({})",
+            dft_size,
+            self.segment_size
+        );
+        let complex = S::COMPLEX || self.two_sided;
+        let n = if complex { dft_size } else { dft_size / 2 + 1 };
+        Self {
+            n_segment: 0,
+            dft_size,
+            sum: vec![T::zero(); n],
+            plan: DftPlan::new(dft_size, complex),
+            ..self
+        }
+    }
+    /// Returns the two-sided spectrum of real signals (`false` by default)
+    ///
+    /// The segments already accumulated are discarded.
+    pub fn two_sided(self, two_sided: bool) -> Self {
+        let dft_size = self.dft_size;
+        Self { two_sided, ..self }.dft_size(dft_size)
+    }
+    /// Sets the segments windowing function
+    ///
+    /// The window is resized to the segment size according to the window [Symmetry]
+    pub fn window(self, window: W, symmetry: Symmetry) -> Self {
+        Self {
+            window: window.resize(self.segment_size, symmetry),
+            ..self
+        }
+    }
+    /// Sets the periodogram [Scaling] ([Scaling::Density] by default)
+    pub fn scaling(self, scaling: Scaling) -> Self {
+        Self { scaling, ..self }
+    }
+    /// Sets the periodogram [WindowCorrection] ([WindowCorrection::Auto] by default)
+    pub fn window_correction(self, correction: WindowCorrection) -> Self {
+        Self { correction, ..self }
+    }
+    /// Sets the segments [Detrend] method
+    pub fn detrend(self, detrend: Detrend<T>) -> Self {
+        Self { detrend, ..self }
+    }
+    /// Pushes new signal `samples` into the estimator
+    pub fn push(&mut self, samples: &[S]) {
+        self.tail.extend_from_slice(samples);
+        self.process();
+    }
+    // Processes all the complete segments in the pending samples
+    fn process(&mut self) {
+        let l = self.segment_size;
+        let d = self.overlap_idx;
+        let mut start = 0;
+        let mut scratch = self.plan.make_scratch_vec();
+        while self.tail.len() - start >= l {
+            let segment = &self.tail[start..start + l];
+            let weights = self.window.weights();
+            let dft = if self.detrend.is_some() {
+                self.plan
+                    .process(&self.detrend.apply(segment), weights, &mut scratch)
+            } else {
+                self.plan.process(segment, weights, &mut scratch)
+            };
+            T::accumulate(&mut self.sum, &T::norm_sqr(&dft));
+            self.n_segment += 1;
+            start += d;
+        }
+        self.tail.drain(..start.min(self.tail.len()));
+    }
+    /// Discards the accumulated segments and the pending samples
+    pub fn reset(&mut self) {
+        self.n_segment = 0;
+        self.tail.clear();
+        self.sum.iter_mut().for_each(|a| *a = T::zero());
+    }
+    /// Returns the current periodogram scaled according to [WelchAccumulator::scaling](WelchAccumulator#structfield.scaling)
+    pub fn periodogram(&self) -> Periodogram<T> {
+        self.scaled_periodogram(self.scaling)
+    }
+    /// Returns the current periodogram scaled according to `scaling`
+    pub fn scaled_periodogram(&self, scaling: Scaling) -> Periodogram<T> {
+        let u = segments_scale(
+            scaling,
+            self.correction,
+            &self.window,
+            self.dft_size,
+            self.fs,
+        ) / T::from_usize(self.n_segment.max(1)).unwrap();
+        let mut values: Vec<T> = self.sum.iter().map(|&x| x * u).collect();
+        let periodogram = if S::COMPLEX || self.two_sided {
+            Periodogram::two_sided(self.fs, values)
+        } else {
+            one_sided(&mut values, self.dft_size);
+            Periodogram::from_values(self.fs, self.dft_size, values)
+        };
+        amplitude_spectrum(scaling, periodogram)
+    }
+    /// Returns the current spectral density (signal unit squared per Hertz)
+    pub fn spectral_density(&self) -> Periodogram<T> {
+        self.scaled_periodogram(Scaling::Density)
+    }
+    /// Returns the current power spectrum (signal unit squared)
+    pub fn power_spectrum(&self) -> Periodogram<T> {
+        self.scaled_periodogram(Scaling::Spectrum)
+    }
+}
+/// Pushes the samples of an iterator into the estimator
+///
+/// The samples are buffered one segment at a time, the whole signal is never held in memory.
+impl<T: Signal, W: Window<T>, S: Sample<T>> Extend<S> for WelchAccumulator<T, W, S> {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        loop {
+            let n = self.segment_size - self.tail.len().min(self.segment_size - 1);
+            let len = self.tail.len();
+            self.tail.extend(iter.by_ref().take(n));
+            if self.tail.len() == len {
+                break;
+            }
+            self.process();
+        }
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Build<WelchAccumulator<T, W, S>>
+    for Builder<'a, T, W, S>
+{
+    fn build(&self) -> WelchAccumulator<T, W, S> {
+        if let Err(e) = self.validate_streaming() {
+            panic!("{}", e)
+        }
+        self.accumulator()
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Builder<'a, T, W, S> {
+    // Returns a new [WelchAccumulator] with the builder settings
+    fn accumulator(&self) -> WelchAccumulator<T, W, S> {
+        let (_, l, m) = self.dimensions();
+        let complex = S::COMPLEX || self.two_sided;
+        WelchAccumulator {
+            n_segment: 0,
+            segment_size: l,
+            dft_size: m,
+            overlap_idx: self.overlap_idx(l),
+            fs: self.fs.unwrap_or_else(T::one),
+            window: self.window.as_ref().map_or_else(
+                || W::with_symmetry(l, self.symmetry),
+                |window| window.resize(l, self.symmetry),
+            ),
+            scaling: self.scaling,
+            correction: self.correction,
+            detrend: self.detrend.clone(),
+            two_sided: self.two_sided,
+            tail: Vec::with_capacity(l),
+            sum: vec![T::zero(); if complex { m } else { m / 2 + 1 }],
+            plan: DftPlan::new(m, complex),
+        }
+    }
+    /// Returns the [WelchAccumulator] of the samples of the iterator `signal`
+    ///
+    /// The accumulator has the segmentation, the window, the detrending and the scaling of the [Builder],
+    /// the segmentation being derived from the signal length (see [Builder::from_len]),
+    /// and the samples are pushed into it with [Extend::extend], one segment at a time.
+    /// Returns an error if the settings are invalid or if the settings of the signal as a whole are set:
+    /// an [Averaging] other than [Averaging::Mean], a [decimation](Builder::decimation), a [mask](Builder::mask),
+    /// a [calibration](Builder::calibration), a [NanPolicy] other than [NanPolicy::Propagate], a cross signal
+    /// or the CUDA [Backend].
+    pub fn accumulate<I: IntoIterator<Item = S>>(
+        &self,
+        signal: I,
+    ) -> Result<WelchAccumulator<T, W, S>, WelchError> {
+        self.validate_streaming()?;
+        let mut accumulator = self.accumulator();
+        accumulator.extend(signal);
+        Ok(accumulator)
+    }
+    // Checks the builder settings of a streaming estimator
+    fn validate_streaming(&self) -> Result<(), WelchError> {
+        self.validate()?;
+        let unsupported = if self.averaging != Averaging::Mean {
+            Some("averaging method")
+        } else if self.decimation > 1 {
+            Some("decimation")
+        } else if self.mask.is_some() {
+            Some("validity mask")
+        } else if self.calibration.is_some() {
+            Some("calibration")
+        } else if self.nan_policy != NanPolicy::Propagate {
+            Some("NaN policy")
+        } else if self.cross_signal.is_some() {
+            Some("cross signal")
+        } else if self.backend != Backend::Cpu {
+            Some("CUDA backend")
+        } else {
+            None
+        };
+        unsupported.map_or(Ok(()), |setting| {
+            Err(WelchError::UnsupportedStreamingSetting(setting))
+        })
+    }
+}
+impl<T: Signal, W: Window<T>, S: Sample<T>> Display for WelchAccumulator<T, W, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Streaming Welch spectral density estimator:")?;
+        writeln!(f, " - number of segment: {:>6}", self.n_segment)?;
+        writeln!(f, " - segment size     : {:>6}", self.segment_size)?;
+        writeln!(
+            f,
+            " - overlap size     : {:>6}",
+            self.segment_size - self.overlap_idx
+        )?;
+        write!(f, " - dft size         : {:>6}", self.dft_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Build, Builder, Detrend, Hann, Scaling, Welch, WelchError};
+
+    // Tone over a drift, given by an iterator
+    fn signal(n: usize) -> impl Iterator<Item = f64> {
+        (0..n).map(|i| (i as f64 * 0.3).sin() + 1e-3 * i as f64)
+    }
+
+    #[test]
+    fn builder_iterator() {
+        let n = 10_000;
+        let builder = Builder::<f64, Hann<f64>>::from_len(n)
+            .sampling_frequency(100.)
+            .n_segment(16)
+            .detrend(Detrend::Linear)
+            .scaling(Scaling::RmsAmplitude);
+        let accumulator = builder.accumulate(signal(n)).unwrap();
+        let x: Vec<f64> = signal(n).collect();
+        let welch: Welch<f64, Hann<f64>> = Builder::new(&x)
+            .sampling_frequency(100.)
+            .n_segment(16)
+            .detrend(Detrend::Linear)
+            .scaling(Scaling::RmsAmplitude)
+            .build();
+        assert_eq!(accumulator.n_segment, welch.n_segment);
+        let (p, q) = (accumulator.periodogram(), welch.periodogram());
+        assert_eq!(p.len(), q.len());
+        p.iter()
+            .zip(q.iter())
+            .for_each(|((_, a), (_, b))| assert!((a - b).abs() <= 1e-12 * b.abs().max(1.)));
+        let masked = builder.mask(&[true; 10_000]).accumulate(signal(n));
+        assert!(matches!(
+            masked,
+            Err(WelchError::UnsupportedStreamingSetting(_))
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    chunks::Samples, dft::DftPlan, Averaging, Build, Builder, Calibration, Detrend, NanPolicy,
+    Sample, Scaling, Signal, Welch, WelchError, Window, WindowCorrection,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Reusable Welch estimator plan
+///
+/// The plan holds the settings of a [Welch] estimator that do not depend on the signal samples:
+/// the segmentation, the window weights and the discrete Fourier transform plan.
+/// It is built once with [Build::build] from the [Builder] returned by [WelchPlan::builder] given the length of the records,
+/// or from a [Builder] given a record representative of the signals to process,
+/// and [WelchPlan::estimate] returns the [Welch] estimator of any record of the same length
+/// without recomputing the window and the Fourier transform plan.
+/// Unlike [Welch::with_signal], the plan does not borrow the signal it is built with.
+///
+/// The NaN samples of each record are handled according to the [NanPolicy] of the plan,
+/// and the gaps of each record are given by the validity mask of the plan ([Builder::mask]);
+/// the records are checked against the [NanPolicy] and the mask by [WelchPlan::estimate].
+#[derive(Debug, Clone)]
+pub struct WelchPlan<T: Signal, W: Window<T>, S: Sample<T> = T> {
+    /// number of segments (`k`) of a record without invalid segments
+    pub n_segment: usize,
+    /// size of each segment (`l`)
+    pub segment_size: usize,
+    /// size of the discrete Fourier transform (`p`)
+    pub dft_size: usize,
+    /// overlaps starting points
+    pub(crate) overlap_idx: usize,
+    /// length of the records, before decimation
+    pub(crate) signal_len: usize,
+    /// the signal sampling frequency `[Hz]`, after decimation
+    pub fs: T,
+    /// segments windowing function
+    pub window: W,
+    /// segments periodogram averaging method
+    pub averaging: Averaging,
+    /// periodogram scaling
+    pub scaling: Scaling,
+    /// periodogram window correction
+    pub correction: WindowCorrection,
+    /// segments detrending
+    pub detrend: Detrend<T>,
+    /// two-sided spectrum of real signals
+    pub(crate) two_sided: bool,
+    /// signal decimation factor
+    pub(crate) decimation: usize,
+    /// handling of the NaN samples
+    pub(crate) nan_policy: NanPolicy,
+    /// validity of the records samples, `false` within the gaps
+    pub(crate) mask: Option<Vec<bool>>,
+    /// signal calibration
+    pub(crate) calibration: Option<Arc<Calibration<T>>>,
+    /// discrete Fourier transform plan
+    pub(crate) plan: DftPlan<T>,
+    /// CUDA GPU plan, if the [Backend::Cuda](crate::Backend) backend is selected
+    #[cfg(feature = "cuda")]
+    pub(crate) cuda: Option<Arc<crate::cuda::CudaPlan>>,
+    /// the type of the signal samples
+    sample: PhantomData<S>,
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Build<WelchPlan<T, W, S>> for Builder<'a, T, W, S> {
+    fn build(&self) -> WelchPlan<T, W, S> {
+        if let Err(e) = self.validate() {
+            panic!("{}", e)
+        }
+        let (k, l, m) = self.dimensions();
+        WelchPlan {
+            n_segment: k,
+            segment_size: l,
+            dft_size: m,
+            overlap_idx: self.overlap_idx(l),
+            signal_len: self.signal.len(),
+            fs: self.fs.unwrap_or_else(T::one) / T::from_usize(self.decimation).unwrap(),
+            window: self.window.as_ref().map_or_else(
+                || W::with_symmetry(l, self.symmetry),
+                |window| window.resize(l, self.symmetry),
+            ),
+            averaging: self.averaging,
+            scaling: self.scaling,
+            correction: self.correction,
+            detrend: self.detrend.clone(),
+            two_sided: self.two_sided,
+            decimation: self.decimation,
+            nan_policy: self.nan_policy,
+            mask: self.mask.as_deref().map(|mask| mask.to_vec()),
+            calibration: self.calibration.clone().map(Arc::new),
+            plan: DftPlan::new(m, S::COMPLEX || self.two_sided),
+            #[cfg(feature = "cuda")]
+            cuda: match self.backend {
+                crate::Backend::Cpu => None,
+                crate::Backend::Cuda(ordinal) => Some(Arc::new(
+                    crate::cuda::CudaPlan::new(ordinal).unwrap_or_else(|e| panic!("{}", e)),
+                )),
+            },
+            sample: PhantomData,
+        }
+    }
+}
+impl<T: Signal, W: Window<T>, S: Sample<T>> WelchPlan<T, W, S> {
+    /// Returns the [Builder] of a plan for records of `record_len` samples sampled at `fs`Hz
+    ///
+    /// The [Builder] holds the records length but no samples, it only builds a [WelchPlan].
+    /// The settings are checked against the records length when the plan is built,
+    /// the samples of each record being checked by [WelchPlan::estimate]
+    pub fn builder(record_len: usize, fs: T) -> Builder<'static, T, W, S> {
+        Builder::from_len(record_len).sampling_frequency(fs)
+    }
+    /// Returns the [Welch] estimator of the `signal`
+    ///
+    /// Returns an error if the `signal` length differs from the length of the records of the plan,
+    /// if the `signal` has NaN samples with [NanPolicy::Error] or if all its segments are invalid
+    pub fn estimate<'a>(&self, signal: &'a [S]) -> Result<Welch<'a, T, W, S>, WelchError> {
+        self.estimate_samples(signal.into())
+    }
+    /// Returns the [Welch] estimator of a signal split into `chunks`
+    ///
+    /// Returns the same errors than [WelchPlan::estimate]
+    pub fn estimate_chunks<'a>(
+        &self,
+        chunks: &'a [&'a [S]],
+    ) -> Result<Welch<'a, T, W, S>, WelchError> {
+        self.estimate_samples(Samples::chunked(chunks))
+    }
+    // Checks the `signal` against the plan and returns its [Welch] estimator
+    fn estimate_samples<'a>(
+        &self,
+        signal: Samples<'a, S>,
+    ) -> Result<Welch<'a, T, W, S>, WelchError> {
+        if signal.len() != self.signal_len {
+            return Err(WelchError::RecordLength {
+                record_len: signal.len(),
+                plan_len: self.signal_len,
+            });
+        }
+        self.nan_policy.check(
+            &signal,
+            self.mask.as_deref(),
+            (self.segment_size, self.overlap_idx, self.decimation),
+        )?;
+        Ok(Welch::new(self, signal))
+    }
+    /// Returns the length of the records the plan applies to
+    pub fn signal_len(&self) -> usize {
+        self.signal_len
+    }
+}
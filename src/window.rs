@@ -1,41 +1,782 @@
-use crate::Signal;
+use crate::{autocorrelation, CorrelationBias, Signal};
+use num_complex::Complex;
+use rustfft::FftPlanner;
+use std::sync::Arc;
 
 /// Signal windowing interface
 pub trait Window<T: Signal>: std::fmt::Debug + Clone {
-    /// Creates a new window of size `n`
+    /// Creates a new symmetric window of size `n`
     fn new(n: usize) -> Self;
     /// Return the window sampling weights
     fn weights(&self) -> &[T];
+    /// Returns a window of size `n` with the same parameters than `self` and the given [Symmetry]
+    ///
+    /// This is used by the [Builder](crate::Builder) to fit a window instance to the segment size
+    fn resize(&self, n: usize, _symmetry: Symmetry) -> Self {
+        Self::new(n)
+    }
+    /// Creates a new window of size `n` with the default parameters and the given [Symmetry]
+    ///
+    /// The weights are computed once, the default parameters being taken from a window of size 1
+    fn with_symmetry(n: usize, symmetry: Symmetry) -> Self {
+        Self::new(1).resize(n, symmetry)
+    }
+    /// Returns `true` if the window cannot be resized and its size must match the segment size
+    fn fixed_size(&self) -> bool {
+        false
+    }
+    /// Returns the smallest size of the window
+    fn min_size(&self) -> usize {
+        1
+    }
     /// Return the sum of the squared weights
-    fn sqr_sum(&self) -> T;
+    fn sqr_sum(&self) -> T {
+        self.weights().iter().map(|&w| w * w).sum()
+    }
     /// Return the square of the weights sum
-    fn sum_sqr(&self) -> T;
+    fn sum_sqr(&self) -> T {
+        self.weights().iter().cloned().sum::<T>().powi(2)
+    }
+    /// Returns the coherent gain, the mean of the weights
+    fn coherent_gain(&self) -> T {
+        self.weights().iter().cloned().sum::<T>() / T::from_usize(self.weights().len()).unwrap()
+    }
+    /// Returns the equivalent noise bandwidth in bins
+    ///
+    /// The bandwidth in Hertz is obtained by multiplying with `fs/n`, where `n` is the window size
+    /// and `fs` the sampling frequency
+    fn enbw(&self) -> T {
+        T::from_usize(self.weights().len()).unwrap() * self.sqr_sum() / self.sum_sqr()
+    }
+    /// Returns the worst case scalloping loss in dB
+    ///
+    /// The loss is the amplitude attenuation of a tone located halfway between 2 bins
+    fn scalloping_loss(&self) -> T {
+        let n = T::from_usize(self.weights().len()).unwrap();
+        let pi = T::from_f64(std::f64::consts::PI).unwrap();
+        let (re, im) = self
+            .weights()
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| {
+                let phase = pi * T::from_usize(i).unwrap() / n;
+                (w * phase.cos(), w * phase.sin())
+            })
+            .fold((T::zero(), T::zero()), |(a, b), (re, im)| (a + re, b + im));
+        let sum = self.weights().iter().cloned().sum::<T>();
+        -T::from_f64(20.).unwrap() * ((re * re + im * im).sqrt() / sum).log10()
+    }
+}
+/// Window symmetry
+///
+/// A symmetric window is used for filter design whereas a periodic window,
+/// which is a symmetric window of size `n+1` truncated to the first `n` samples,
+/// is the natural choice for spectral analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symmetry {
+    /// Periodic window (default)
+    #[default]
+    Periodic,
+    /// Symmetric window
+    Symmetric,
+}
+impl Symmetry {
+    // Returns the `n` weights of the window given the function `symmetric` that computes the weights of a symmetric window
+    fn weights<T: Signal, F: Fn(usize) -> Vec<T>>(self, n: usize, symmetric: F) -> Vec<T> {
+        if n == 1 {
+            return vec![T::one()];
+        }
+        match self {
+            Symmetry::Symmetric => symmetric(n),
+            Symmetry::Periodic => {
+                let mut weight = symmetric(n + 1);
+                weight.truncate(n);
+                weight
+            }
+        }
+    }
+}
+// Computes the `n` weights of a cosine-sum window `w(i) = sum_k (-1)^k a_k cos(2 pi k i/(n-1))`,
+// a window of size 1 being a single unit weight as for all the windows defined over `n-1` intervals
+fn cosine_sum<T: Signal>(n: usize, a: &[f64]) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let two_pi = 2. * std::f64::consts::PI;
+    let nm1 = (n - 1) as f64;
+    (0..n)
+        .map(|i| {
+            let x = two_pi * i as f64 / nm1;
+            a.iter()
+                .enumerate()
+                .map(|(k, a)| if k % 2 == 0 { *a } else { -a } * (x * k as f64).cos())
+                .sum::<f64>()
+        })
+        .map(|w| T::from_f64(w).unwrap())
+        .collect()
 }
 /// Hann window
 #[derive(Debug, Clone)]
 pub struct Hann<T> {
     weight: Vec<T>,
 }
+fn hann<T: Signal>(n: usize) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let pi = T::from_f64(std::f64::consts::PI).unwrap();
+    let nm1 = T::from_usize(n - 1).unwrap();
+    (0..n)
+        .map(|i| {
+            let j = T::from_usize(i).unwrap();
+            (pi * j / nm1).sin().powi(2)
+        })
+        .collect()
+}
 impl<T: Signal> Window<T> for Hann<T> {
     fn new(n: usize) -> Self {
-        let pi = T::from_f64(std::f64::consts::PI).unwrap();
-        let nm1 = T::from_usize(n - 1).unwrap();
+        Self { weight: hann(n) }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            weight: symmetry.weights(n, hann),
+        }
+    }
+}
+/// Hamming window
+#[derive(Debug, Clone)]
+pub struct Hamming<T> {
+    weight: Vec<T>,
+}
+impl<T: Signal> Window<T> for Hamming<T> {
+    fn new(n: usize) -> Self {
+        Self {
+            weight: cosine_sum(n, &[0.54, 0.46]),
+        }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            weight: symmetry.weights(n, |n| cosine_sum(n, &[0.54, 0.46])),
+        }
+    }
+}
+/// Blackman window
+#[derive(Debug, Clone)]
+pub struct Blackman<T> {
+    weight: Vec<T>,
+}
+impl<T: Signal> Window<T> for Blackman<T> {
+    fn new(n: usize) -> Self {
+        Self {
+            weight: cosine_sum(n, &[0.42, 0.5, 0.08]),
+        }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            weight: symmetry.weights(n, |n| cosine_sum(n, &[0.42, 0.5, 0.08])),
+        }
+    }
+}
+/// Blackman-Harris window
+///
+/// The minimum 4-term Blackman-Harris window with a highest sidelobe level of about -92dB
+#[derive(Debug, Clone)]
+pub struct BlackmanHarris<T> {
+    weight: Vec<T>,
+}
+impl<T: Signal> Window<T> for BlackmanHarris<T> {
+    fn new(n: usize) -> Self {
+        Self {
+            weight: cosine_sum(n, &[0.35875, 0.48829, 0.14128, 0.01168]),
+        }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            weight: symmetry.weights(n, |n| cosine_sum(n, &[0.35875, 0.48829, 0.14128, 0.01168])),
+        }
+    }
+}
+/// Bartlett window
+///
+/// A triangular window with zero valued end points
+#[derive(Debug, Clone)]
+pub struct Bartlett<T> {
+    weight: Vec<T>,
+}
+fn bartlett<T: Signal>(n: usize) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let nm1 = T::from_usize(n - 1).unwrap();
+    let two = T::from_f64(2.).unwrap();
+    (0..n)
+        .map(|i| {
+            let j = T::from_usize(i).unwrap();
+            T::one() - (two * j / nm1 - T::one()).abs()
+        })
+        .collect()
+}
+impl<T: Signal> Window<T> for Bartlett<T> {
+    fn new(n: usize) -> Self {
+        Self {
+            weight: bartlett(n),
+        }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            weight: symmetry.weights(n, bartlett),
+        }
+    }
+}
+/// Parabolic window
+///
+/// Also known as the Welch window, it is a parabola with zero valued end points
+#[derive(Debug, Clone)]
+pub struct Parabolic<T> {
+    weight: Vec<T>,
+}
+fn parabolic<T: Signal>(n: usize) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let nm1 = T::from_usize(n - 1).unwrap();
+    let two = T::from_f64(2.).unwrap();
+    (0..n)
+        .map(|i| {
+            let j = T::from_usize(i).unwrap();
+            T::one() - (two * j / nm1 - T::one()).powi(2)
+        })
+        .collect()
+}
+impl<T: Signal> Window<T> for Parabolic<T> {
+    fn new(n: usize) -> Self {
+        Self {
+            weight: parabolic(n),
+        }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            weight: symmetry.weights(n, parabolic),
+        }
+    }
+}
+/// Generic cosine-sum window
+///
+/// The window weights are given by `w(i) = sum_k (-1)^k a_k cos(2 pi k i/(n-1))` for a user defined
+/// list of coefficients `a_k`, e.g. `[0.5,0.5]` for [Hann], `[0.54,0.46]` for [Hamming] or
+/// `[0.42,0.5,0.08]` for [Blackman].
+/// [Window::new] uses the [Hann] coefficients, other coefficients are set with [CosineSum::with_coefficients]
+/// and the window is given to the estimator with [Builder::window](crate::Builder::window).
+#[derive(Debug, Clone)]
+pub struct CosineSum<T> {
+    coefficients: Vec<T>,
+    weight: Vec<T>,
+}
+impl<T: Signal> CosineSum<T> {
+    /// Creates a new cosine-sum window of size `n` from the `coefficients` `a_k`
+    pub fn with_coefficients(n: usize, coefficients: &[T]) -> Self {
+        let a: Vec<f64> = coefficients.iter().map(|a| a.to_f64().unwrap()).collect();
+        Self {
+            coefficients: coefficients.to_vec(),
+            weight: cosine_sum(n, &a),
+        }
+    }
+    /// Returns the cosine coefficients
+    pub fn coefficients(&self) -> &[T] {
+        self.coefficients.as_slice()
+    }
+}
+impl<T: Signal> Window<T> for CosineSum<T> {
+    fn new(n: usize) -> Self {
+        Self::with_coefficients(n, &[T::from_f64(0.5).unwrap(), T::from_f64(0.5).unwrap()])
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        let a: Vec<f64> = self
+            .coefficients
+            .iter()
+            .map(|a| a.to_f64().unwrap())
+            .collect();
+        Self {
+            coefficients: self.coefficients.clone(),
+            weight: symmetry.weights(n, |n| cosine_sum(n, &a)),
+        }
+    }
+}
+// Zeroth order modified Bessel function of the first kind
+fn bessel_i0(x: f64) -> f64 {
+    let y = 0.25 * x * x;
+    let mut term = 1f64;
+    let mut sum = 1f64;
+    let mut k = 1f64;
+    while term > sum * f64::EPSILON {
+        term *= y / (k * k);
+        sum += term;
+        k += 1.;
+    }
+    sum
+}
+fn kaiser<T: Signal>(n: usize, beta: T) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let b = beta.to_f64().unwrap();
+    let i0_b = bessel_i0(b);
+    let nm1 = (n - 1) as f64;
+    (0..n)
+        .map(|i| {
+            let x = 2. * i as f64 / nm1 - 1.;
+            bessel_i0(b * (1. - x * x).sqrt()) / i0_b
+        })
+        .map(|w| T::from_f64(w).unwrap())
+        .collect()
+}
+/// Kaiser window
+///
+/// The window shape is set with the parameter `beta`, trading main lobe width for sidelobe level:
+/// `beta=0` gives a rectangular window, `beta=5` is close to a [Hamming] window and `beta=8.6` to a [Blackman] window.
+/// [Window::new] uses `beta=8.6`, other values are set with [Kaiser::with_beta] and the window
+/// is given to the estimator with [Builder::window](crate::Builder::window).
+#[derive(Debug, Clone)]
+pub struct Kaiser<T> {
+    beta: T,
+    weight: Vec<T>,
+}
+impl<T: Signal> Kaiser<T> {
+    /// Creates a new Kaiser window of size `n` with the shape parameter `beta`
+    pub fn with_beta(n: usize, beta: T) -> Self {
+        Self {
+            beta,
+            weight: kaiser(n, beta),
+        }
+    }
+    /// Returns the window shape parameter
+    pub fn beta(&self) -> T {
+        self.beta
+    }
+}
+impl<T: Signal> Window<T> for Kaiser<T> {
+    fn new(n: usize) -> Self {
+        Self::with_beta(n, T::from_f64(8.6).unwrap())
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            beta: self.beta,
+            weight: symmetry.weights(n, |n| kaiser(n, self.beta)),
+        }
+    }
+}
+fn chebyshev<T: Signal>(n: usize, attenuation: T) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let m = n as f64;
+    let order = m - 1.;
+    let beta = ((10f64.powf(attenuation.to_f64().unwrap().abs() / 20.)).acosh() / order).cosh();
+    // Chebyshev polynomial sampled in the frequency domain
+    let mut buffer: Vec<Complex<f64>> = (0..n)
+        .map(|k| {
+            let x = beta * (std::f64::consts::PI * k as f64 / m).cos();
+            let p = if x > 1. {
+                (order * x.acosh()).cosh()
+            } else if x < -1. {
+                (2. * (n % 2) as f64 - 1.) * (order * (-x).acosh()).cosh()
+            } else {
+                (order * x.acos()).cos()
+            };
+            if n % 2 == 1 {
+                Complex::new(p, 0.)
+            } else {
+                Complex::from_polar(p, std::f64::consts::PI * k as f64 / m)
+            }
+        })
+        .collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut buffer);
+    let h = n / 2 + 1;
+    let w: Vec<f64> = if n % 2 == 1 {
+        buffer[1..h]
+            .iter()
+            .rev()
+            .chain(&buffer[..h])
+            .map(|c| c.re)
+            .collect()
+    } else {
+        buffer[1..h]
+            .iter()
+            .rev()
+            .chain(&buffer[1..h])
+            .map(|c| c.re)
+            .collect()
+    };
+    let w_max = w.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    w.into_iter()
+        .map(|w| T::from_f64(w / w_max).unwrap())
+        .collect()
+}
+/// Dolph-Chebyshev window
+///
+/// The window has equiripple sidelobes at the level set by the attenuation parameter in dB.
+/// [Window::new] uses an attenuation of 100dB, other values are set with [DolphChebyshev::with_attenuation]
+/// and the window is given to the estimator with [Builder::window](crate::Builder::window).
+#[derive(Debug, Clone)]
+pub struct DolphChebyshev<T> {
+    attenuation: T,
+    weight: Vec<T>,
+}
+impl<T: Signal> DolphChebyshev<T> {
+    /// Creates a new Dolph-Chebyshev window of size `n` with sidelobes `attenuation` dB below the main lobe
+    pub fn with_attenuation(n: usize, attenuation: T) -> Self {
+        Self {
+            attenuation,
+            weight: chebyshev(n, attenuation),
+        }
+    }
+    /// Returns the sidelobe attenuation in dB
+    pub fn attenuation(&self) -> T {
+        self.attenuation
+    }
+}
+impl<T: Signal> Window<T> for DolphChebyshev<T> {
+    fn new(n: usize) -> Self {
+        Self::with_attenuation(n, T::from_f64(100.).unwrap())
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            attenuation: self.attenuation,
+            weight: symmetry.weights(n, |n| chebyshev(n, self.attenuation)),
+        }
+    }
+}
+/// Exponential window
+///
+/// A one-sided exponential decay `w(i)=exp(-i/tau)`, with the decay constant `tau` in samples,
+/// as used on the response channel of impact and ring-down measurements.
+/// [Window::new] sets `tau` such as the last sample is attenuated by 40dB, other values are set with
+/// [Exponential::with_decay] and the window is given to the estimator with [Builder::window](crate::Builder::window).
+#[derive(Debug, Clone)]
+pub struct Exponential<T> {
+    tau: T,
+    weight: Vec<T>,
+}
+impl<T: Signal> Exponential<T> {
+    /// Creates a new exponential window of size `n` with the decay constant `tau` in samples
+    pub fn with_decay(n: usize, tau: T) -> Self {
         let weight: Vec<T> = (0..n)
-            .map(|i| {
-                let j = T::from_usize(i).unwrap();
-                (pi * j / nm1).sin().powi(2)
-            })
+            .map(|i| (-T::from_usize(i).unwrap() / tau).exp())
             .collect();
-        Self { weight }
+        Self { tau, weight }
+    }
+    /// Returns the decay constant in samples
+    pub fn tau(&self) -> T {
+        self.tau
+    }
+}
+impl<T: Signal> Window<T> for Exponential<T> {
+    fn new(n: usize) -> Self {
+        let tau = T::from_usize(n.max(2) - 1).unwrap() / T::from_f64(100f64.ln()).unwrap();
+        Self::with_decay(n, tau)
     }
     fn weights(&self) -> &[T] {
         self.weight.as_slice()
     }
-    fn sqr_sum(&self) -> T {
-        self.weights().iter().map(|&w| w * w).sum()
+    fn resize(&self, n: usize, _symmetry: Symmetry) -> Self {
+        Self::with_decay(n, self.tau)
     }
-    fn sum_sqr(&self) -> T {
-        self.weights().iter().cloned().sum::<T>().powi(2)
+    fn with_symmetry(n: usize, _symmetry: Symmetry) -> Self {
+        Self::new(n)
+    }
+}
+// Discrete prolate spheroidal sequence of order `k`, size `n` and time-bandwidth product `nw`
+//
+// The sequence is the eigenvector associated with the k-th largest eigenvalue of the symmetric
+// tridiagonal matrix that commutes with the time and band limiting operator.
+// The eigenvalue is found by bisection and the eigenvector by inverse iteration.
+fn dpss<T: Signal>(n: usize, nw: f64, k: usize) -> Vec<T> {
+    if n == 1 {
+        return vec![T::one()];
+    }
+    let cos_w = (2. * std::f64::consts::PI * nw / n as f64).cos();
+    let d: Vec<f64> = (0..n)
+        .map(|i| (0.5 * (n as f64 - 1. - 2. * i as f64)).powi(2) * cos_w)
+        .collect();
+    let e: Vec<f64> = (1..n).map(|i| 0.5 * (i * (n - i)) as f64).collect();
+    // number of eigenvalues lower than x (Sturm sequence)
+    let n_lower = |x: f64| {
+        let mut count = 0;
+        let mut q = 1f64;
+        for i in 0..n {
+            let e2 = if i > 0 { e[i - 1] * e[i - 1] } else { 0. };
+            q = d[i] - x - if i > 0 { e2 / q } else { 0. };
+            if q == 0. {
+                q = f64::EPSILON * (d[i].abs() + 1.);
+            }
+            if q < 0. {
+                count += 1;
+            }
+        }
+        count
+    };
+    let radius = (0..n)
+        .map(|i| d[i].abs() + if i > 0 { e[i - 1] } else { 0. } + if i < n - 1 { e[i] } else { 0. })
+        .fold(0f64, f64::max);
+    let (mut lo, mut hi) = (-radius, radius);
+    let rank = n - 1 - k;
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if n_lower(mid) > rank {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+        if hi - lo <= f64::EPSILON * radius {
+            break;
+        }
+    }
+    let lambda = 0.5 * (lo + hi) + f64::EPSILON.sqrt() * radius * 1e-3;
+    // inverse iteration with the Thomas algorithm
+    let mut v = vec![1f64; n];
+    for _ in 0..4 {
+        let mut c = vec![0f64; n];
+        let mut x = vec![0f64; n];
+        let mut b = d[0] - lambda;
+        c[0] = if n > 1 { e[0] / b } else { 0. };
+        x[0] = v[0] / b;
+        for i in 1..n {
+            b = d[i] - lambda - e[i - 1] * c[i - 1];
+            if i < n - 1 {
+                c[i] = e[i] / b;
+            }
+            x[i] = (v[i] - e[i - 1] * x[i - 1]) / b;
+        }
+        for i in (0..n - 1).rev() {
+            x[i] -= c[i] * x[i + 1];
+        }
+        let norm = x.iter().map(|x| x * x).sum::<f64>().sqrt();
+        v = x.into_iter().map(|x| x / norm).collect();
+    }
+    // sign convention: positive mean for symmetric sequences, positive first lobe otherwise
+    let s: f64 = if k % 2 == 1 {
+        v.iter()
+            .enumerate()
+            .map(|(i, v)| (n as f64 - 1. - 2. * i as f64) * v)
+            .sum()
+    } else {
+        v.iter().sum()
+    };
+    if s < 0. {
+        v.iter_mut().for_each(|v| *v = -*v);
+    }
+    v.into_iter().map(|v| T::from_f64(v).unwrap()).collect()
+}
+/// Discrete prolate spheroidal sequence (Slepian) window
+///
+/// The window of order `k` is the `k`th DPSS taper for the time-bandwidth product `NW`,
+/// the symmetric window is normalized to unit energy.
+/// The zeroth order window maximizes the energy concentration within the bandwidth `[-W,W]`.
+/// [Window::new] uses `NW=4` and `k=0`, other values are set with [Dpss::with_bandwidth]
+/// and the window is given to the estimator with [Builder::window](crate::Builder::window).
+#[derive(Debug, Clone)]
+pub struct Dpss<T> {
+    nw: T,
+    order: usize,
+    weight: Vec<T>,
+}
+impl<T: Signal> Dpss<T> {
+    /// Creates a new DPSS window of size `n`, time-bandwidth product `nw` and order `k`
+    ///
+    /// Panics if the order is not less than the window size
+    pub fn with_bandwidth(n: usize, nw: T, k: usize) -> Self {
+        assert!(
+            k < n,
+            "the DPSS order ({k}) must be less than the window size ({n})"
+        );
+        Self {
+            nw,
+            order: k,
+            weight: dpss(n, nw.to_f64().unwrap(), k),
+        }
+    }
+    /// Returns the time-bandwidth product
+    pub fn nw(&self) -> T {
+        self.nw
+    }
+    /// Returns the order of the sequence
+    pub fn order(&self) -> usize {
+        self.order
+    }
+    // Returns the sequence of order `k` with the size and the time-bandwidth product of `self`
+    // and the given symmetry
+    pub(crate) fn with_order(&self, k: usize, symmetry: Symmetry) -> Self {
+        let n = self.weight.len();
+        assert!(
+            k < n,
+            "the DPSS order ({k}) must be less than the window size ({n})"
+        );
+        let nw = self.nw.to_f64().unwrap();
+        Self {
+            nw: self.nw,
+            order: k,
+            weight: symmetry.weights(n, |n| dpss(n, nw, k)),
+        }
+    }
+    /// Returns the concentration `lambda`, the fraction of the energy of the sequence within the bandwidth `[-W,W]`
+    ///
+    /// The concentration is the eigenvalue of the sequence for the time and band limiting operator, `W=NW/n`,
+    /// and it is close to 1 for the orders lower than `2NW-1`.
+    /// It is computed from the autocorrelation `r` of the sequence as `(2Wr(0)+2sum(r(k)sin(2pi Wk)/(pi k)))/r(0)`.
+    pub fn concentration(&self) -> T {
+        let n = self.weight.len();
+        let w = self.nw.to_f64().unwrap() / n as f64;
+        let x: Vec<f64> = self.weight.iter().map(|x| x.to_f64().unwrap()).collect();
+        let r = autocorrelation(&x, n - 1, CorrelationBias::Biased);
+        let pi = std::f64::consts::PI;
+        let lambda = r
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold(2. * w * r[0], |l, (k, r)| {
+                l + 2. * r * (2. * pi * w * k as f64).sin() / (pi * k as f64)
+            })
+            / r[0];
+        T::from_f64(lambda).unwrap()
+    }
+}
+impl<T: Signal> Window<T> for Dpss<T> {
+    fn new(n: usize) -> Self {
+        Self::with_bandwidth(n, T::from_f64(4.).unwrap(), 0)
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        assert!(
+            self.order < n,
+            "the DPSS order ({}) must be less than the window size ({})",
+            self.order,
+            n
+        );
+        let nw = self.nw.to_f64().unwrap();
+        Self {
+            nw: self.nw,
+            order: self.order,
+            weight: symmetry.weights(n, |n| dpss(n, nw, self.order)),
+        }
+    }
+    fn min_size(&self) -> usize {
+        self.order + 1
+    }
+}
+/// Custom window
+///
+/// A window made of user supplied weights, e.g. a measured or calibrated taper.
+/// The window is created with [Custom::from_weights] and given to the estimator
+/// with [Builder::window](crate::Builder::window); the number of weights must match the segment size,
+/// otherwise [Builder::try_build](crate::Builder::try_build) returns [WelchError::WindowSize](crate::WelchError::WindowSize),
+/// and the weights are used as given whatever the [Symmetry].
+/// [Window::new] returns a window where all weights are 1.
+#[derive(Debug, Clone)]
+pub struct Custom<T> {
+    weight: Vec<T>,
+}
+impl<T: Signal> Custom<T> {
+    /// Creates a new window from the given `weights`
+    pub fn from_weights(weights: &[T]) -> Self {
+        Self {
+            weight: weights.to_vec(),
+        }
+    }
+}
+impl<T: Signal> Window<T> for Custom<T> {
+    fn new(n: usize) -> Self {
+        Self {
+            weight: vec![T::one(); n],
+        }
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, _symmetry: Symmetry) -> Self {
+        assert_eq!(
+            self.weight.len(),
+            n,
+            "the custom window size must match the segment size"
+        );
+        self.clone()
+    }
+    fn with_symmetry(n: usize, _symmetry: Symmetry) -> Self {
+        Self::new(n)
+    }
+    fn fixed_size(&self) -> bool {
+        true
+    }
+}
+/// Window defined by a closure
+///
+/// The window weights are given by a closure `f(i,n)` that maps the sample index `i` of a symmetric window of size `n` to a weight.
+/// The window is created with [FnWindow::new] and given to the estimator with [Builder::window](crate::Builder::window).
+/// [Window::new] returns a window where all weights are 1.
+#[derive(Clone)]
+pub struct FnWindow<T> {
+    f: Arc<dyn Fn(usize, usize) -> T + Send + Sync>,
+    weight: Vec<T>,
+}
+impl<T: Signal> FnWindow<T> {
+    /// Creates a new window of size `n` with the weights given by the closure `f(i,n)`
+    pub fn new<F>(n: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> T + Send + Sync + 'static,
+    {
+        Self {
+            weight: (0..n).map(|i| f(i, n)).collect(),
+            f: Arc::new(f),
+        }
+    }
+}
+impl<T: Signal> std::fmt::Debug for FnWindow<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FnWindow")
+            .field("weight", &self.weight)
+            .finish()
+    }
+}
+impl<T: Signal> Window<T> for FnWindow<T> {
+    fn new(n: usize) -> Self {
+        FnWindow::new(n, |_, _| T::one())
+    }
+    fn weights(&self) -> &[T] {
+        self.weight.as_slice()
+    }
+    fn resize(&self, n: usize, symmetry: Symmetry) -> Self {
+        Self {
+            f: self.f.clone(),
+            weight: symmetry.weights(n, |n| (0..n).map(|i| (self.f)(i, n)).collect()),
+        }
     }
 }
 /// One window
@@ -61,3 +802,37 @@ impl<T: Signal> Window<T> for One<T> {
         self.sqr_sum().powi(2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the window created with its symmetry matches the resized default window
+    fn check<W: Window<f64>>() {
+        for n in [1, 2, 33, 64] {
+            for symmetry in [Symmetry::Symmetric, Symmetry::Periodic] {
+                let window = W::with_symmetry(n, symmetry);
+                let expected = W::new(n).resize(n, symmetry);
+                assert_eq!(window.weights(), expected.weights(), "{window:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn with_symmetry() {
+        check::<Hann<f64>>();
+        check::<Hamming<f64>>();
+        check::<Blackman<f64>>();
+        check::<BlackmanHarris<f64>>();
+        check::<Bartlett<f64>>();
+        check::<Parabolic<f64>>();
+        check::<CosineSum<f64>>();
+        check::<Kaiser<f64>>();
+        check::<DolphChebyshev<f64>>();
+        check::<Exponential<f64>>();
+        check::<Dpss<f64>>();
+        check::<Custom<f64>>();
+        check::<FnWindow<f64>>();
+        check::<One<f64>>();
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{Averaging, Periodogram, Sample, Signal, Welch, Window};
+use num_complex::Complex;
+use num_traits::Zero;
+use rustfft::{Fft, FftPlanner};
+use std::sync::Arc;
+
+// Chirp-Z transform of sequences of length `l` on `n` frequencies `f1+k df`, with Bluestein's algorithm
+struct ChirpZ<T: Signal> {
+    forward: Arc<dyn Fft<T>>,
+    inverse: Arc<dyn Fft<T>>,
+    // input chirp `exp(-i pi (df/fs) j^2 - 2 i pi (f1/fs) j)`
+    input: Vec<Complex<T>>,
+    // Fourier transform of the convolution kernel `exp(i pi (df/fs) j^2)`
+    kernel: Vec<Complex<T>>,
+    // output chirp `exp(-i pi (df/fs) k^2)`
+    output: Vec<Complex<T>>,
+}
+impl<T: Signal> ChirpZ<T> {
+    fn new(l: usize, n: usize, f1: f64, df: f64, fs: f64) -> Self {
+        let size = (l + n - 1).next_power_of_two();
+        let mut planner = FftPlanner::new();
+        let forward = planner.plan_fft_forward(size);
+        let inverse = planner.plan_fft_inverse(size);
+        // exp(-i pi x), the phase `x` being reduced modulo 2 for accuracy
+        let cis = |x: f64| {
+            let (s, c) = (std::f64::consts::PI * x.rem_euclid(2.)).sin_cos();
+            Complex::new(T::from_f64(c).unwrap(), T::from_f64(-s).unwrap())
+        };
+        let r = df / fs;
+        let sqr = |j: usize| (j * j) as f64;
+        let input = (0..l)
+            .map(|j| cis(r * sqr(j) + 2. * f1 / fs * j as f64))
+            .collect();
+        let mut kernel = vec![Complex::zero(); size];
+        (0..n).for_each(|j| kernel[j] = cis(-r * sqr(j)));
+        (1..l).for_each(|j| kernel[size - j] = cis(-r * sqr(j)));
+        forward.process(&mut kernel);
+        let output = (0..n).map(|k| cis(r * sqr(k))).collect();
+        Self {
+            forward,
+            inverse,
+            input,
+            kernel,
+            output,
+        }
+    }
+    fn process(&self, x: &[Complex<T>]) -> Vec<Complex<T>> {
+        let size = self.kernel.len();
+        let mut buffer = vec![Complex::zero(); size];
+        buffer
+            .iter_mut()
+            .zip(x.iter().zip(&self.input))
+            .for_each(|(b, (&x, &c))| *b = x * c);
+        self.forward.process(&mut buffer);
+        buffer
+            .iter_mut()
+            .zip(&self.kernel)
+            .for_each(|(b, &k)| *b = *b * k);
+        self.inverse.process(&mut buffer);
+        let u = T::from_usize(size).unwrap().recip();
+        self.output
+            .iter()
+            .zip(buffer)
+            .map(|(&c, b)| c * b * u)
+            .collect()
+    }
+}
+impl<'a, T: Signal, W: Window<T>, S: Sample<T>> Welch<'a, T, W, S> {
+    /// Returns the periodogram within the frequency band `[f1,f2]` `[Hz]` evaluated at `n` evenly spaced frequencies
+    ///
+    /// The Fourier transform of each segment is computed only within the band with the chirp-Z transform,
+    /// such as the frequency resolution is `(f2-f1)/(n-1)` instead of `fs/m`.
+    /// The periodogram is scaled according to [Welch::scaling](Welch#structfield.scaling)
+    /// and the segments are averaged according to [Welch::averaging](Welch#structfield.averaging).
+    /// The frequencies of a one-sided periodogram are doubled, except the zero and Nyquist frequencies.
+    /// Note that the zoom does not improve the spectral resolution of the estimate,
+    /// which is set by the segment size and by the window.
+    pub fn zoom(&self, f1: T, f2: T, n: usize) -> Periodogram<T> {
+        assert!(
+            f2 > f1,
+            "the band upper frequency must be greater than the lower frequency"
+        );
+        assert!(n > 1, "the band requires at least 2 frequencies");
+        let df = (f2 - f1) / T::from_usize(n - 1).unwrap();
+        let czt = ChirpZ::new(
+            self.segment_size,
+            n,
+            f1.to_f64().unwrap(),
+            df.to_f64().unwrap(),
+            self.fs.to_f64().unwrap(),
+        );
+        let u: Vec<T> = (0..n)
+            .map(|k| self.power_scale(f1 + T::from_usize(k).unwrap() * df))
+            .collect();
+        let segments = self.windowed_segments().map(|s| {
+            czt.process(&s)
+                .into_iter()
+                .zip(&u)
+                .map(|(x, &u)| x.norm_sqr() * u)
+                .collect()
+        });
+        let values = self.averaging.average(segments, n);
+        let periodogram = Periodogram::from_band(self.fs, f1, df, values, self.is_two_sided());
+        if self.scaling.is_amplitude() {
+            self.amplitude_spectrum(self.scaling, periodogram)
+        } else if self.averaging == Averaging::Mean {
+            periodogram.with_dof(self.degrees_of_freedom())
+        } else {
+            periodogram
+        }
+    }
+}